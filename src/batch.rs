@@ -0,0 +1,269 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::headless::{headless_app, run_mission};
+use crate::objectives::MissionOutcome;
+use crate::robots::{ExplorationMode, ExploredZones};
+use crate::scenario::{RobotKind, Scenario};
+use crate::stats::SimulationStats;
+
+// Une combinaison de paramètres appliquée au scénario de base
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterSet {
+    pub exploration_mode: Option<ExplorationMode>,
+    pub miners: Option<u32>,
+    pub explorers: Option<u32>,
+}
+
+impl ParameterSet {
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(mode) = self.exploration_mode {
+            parts.push(format!("{:?}", mode));
+        }
+        if let Some(miners) = self.miners {
+            parts.push(format!("{} mineurs", miners));
+        }
+        if let Some(explorers) = self.explorers {
+            parts.push(format!("{} explorateurs", explorers));
+        }
+        if parts.is_empty() {
+            "base".into()
+        } else {
+            parts.join(" / ")
+        }
+    }
+
+    // Les effectifs remplacent le nombre de robots du premier groupe de chaque type
+    pub fn apply(&self, scenario: &Scenario) -> Scenario {
+        let mut scenario = scenario.clone();
+        if let Some(mode) = self.exploration_mode {
+            scenario.exploration_mode = mode;
+        }
+        for (kind, count) in [(RobotKind::Miner, self.miners), (RobotKind::Explorer, self.explorers)] {
+            let Some(count) = count else {
+                continue;
+            };
+            if let Some(spec) = scenario.robots.iter_mut().find(|spec| spec.kind == kind) {
+                spec.count = count;
+            }
+        }
+        scenario
+    }
+}
+
+// Produit cartésien des valeurs demandées; une liste vide laisse le paramètre du scénario
+pub fn parameter_grid(modes: &[ExplorationMode], miners: &[u32], explorers: &[u32]) -> Vec<ParameterSet> {
+    fn options<T: Copy>(values: &[T]) -> Vec<Option<T>> {
+        if values.is_empty() {
+            vec![None]
+        } else {
+            values.iter().copied().map(Some).collect()
+        }
+    }
+
+    let mut grid = Vec::new();
+    for &exploration_mode in &options(modes) {
+        for &miners in &options(miners) {
+            for &explorers in &options(explorers) {
+                grid.push(ParameterSet { exploration_mode, miners, explorers });
+            }
+        }
+    }
+    grid
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchConfig {
+    pub scenario: Scenario,
+    pub seeds: Vec<u32>,
+    pub parameters: Vec<ParameterSet>,
+    pub units: usize,
+    pub max_time: f32,
+    pub threads: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub parameters: ParameterSet,
+    pub seed: u32,
+    pub success: Option<bool>,
+    pub elapsed: f32,
+    pub collection_time: Option<f32>,
+    pub coverage: f32,
+    pub stuck_events: u32,
+    pub units_collected: u32,
+}
+
+pub fn run_single(scenario: Scenario, parameters: &ParameterSet, seed: u32, units: usize, max_time: f32) -> RunResult {
+    let mut app = headless_app(parameters.apply(&scenario).with_seed(seed));
+    let status = run_mission(&mut app, max_time);
+    let stats = app.world.resource::<SimulationStats>();
+
+    RunResult {
+        parameters: parameters.clone(),
+        seed,
+        success: status.outcome.map(|outcome| outcome == MissionOutcome::Success),
+        elapsed: stats.elapsed,
+        collection_time: stats.time_to_collect(units),
+        coverage: app.world.resource::<ExploredZones>().explored_ratio(),
+        stuck_events: stats.stuck_events,
+        units_collected: stats.units_collected(),
+    }
+}
+
+// Répartit les exécutions (paramètres x graines) sur `threads` fils; chaque App est indépendante
+pub fn run_batch(config: &BatchConfig) -> Vec<RunResult> {
+    let jobs: Vec<(usize, u32)> = (0..config.parameters.len())
+        .flat_map(|index| config.seeds.iter().map(move |&seed| (index, seed)))
+        .collect();
+    let next_job = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(jobs.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..config.threads.max(1) {
+            scope.spawn(|| loop {
+                let job = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some(&(index, seed)) = jobs.get(job) else {
+                    break;
+                };
+                let parameters = &config.parameters[index];
+                let result = run_single(config.scenario.clone(), parameters, seed, config.units, config.max_time);
+                eprintln!("✔️ [{}/{}] {} seed {}", job + 1, jobs.len(), parameters.label(), seed);
+                results.lock().unwrap().push((job, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(job, _)| *job);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f32,
+    pub median: f32,
+    pub p10: f32,
+    pub p90: f32,
+}
+
+impl Distribution {
+    pub fn from_values(values: &[f32]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f32::total_cmp);
+
+        Self {
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f32>() / sorted.len() as f32,
+            median: percentile(&sorted, 0.5),
+            p10: percentile(&sorted, 0.1),
+            p90: percentile(&sorted, 0.9),
+        }
+    }
+}
+
+// Percentile par interpolation linéaire sur des valeurs triées
+pub fn percentile(sorted: &[f32], fraction: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = fraction.clamp(0.0, 1.0) * (sorted.len() - 1) as f32;
+    let low = rank.floor() as usize;
+    let high = rank.ceil() as usize;
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f32)
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchSummary {
+    pub parameters: ParameterSet,
+    pub runs: usize,
+    pub successes: usize,
+    pub collection_time: Distribution,
+    pub coverage: Distribution,
+    pub stuck_events: Distribution,
+}
+
+pub fn summarize(parameters: &[ParameterSet], results: &[RunResult]) -> Vec<BatchSummary> {
+    parameters
+        .iter()
+        .map(|set| {
+            let runs: Vec<&RunResult> = results.iter().filter(|result| &result.parameters == set).collect();
+            let collect = |value: &dyn Fn(&RunResult) -> Option<f32>| -> Vec<f32> {
+                runs.iter().filter_map(|result| value(result)).collect()
+            };
+
+            BatchSummary {
+                parameters: set.clone(),
+                runs: runs.len(),
+                successes: runs.iter().filter(|result| result.success == Some(true)).count(),
+                collection_time: Distribution::from_values(&collect(&|result| result.collection_time)),
+                coverage: Distribution::from_values(&collect(&|result| Some(result.coverage))),
+                stuck_events: Distribution::from_values(&collect(&|result| Some(result.stuck_events as f32))),
+            }
+        })
+        .collect()
+}
+
+pub fn write_summary_csv(summaries: &[BatchSummary], mut out: impl Write) -> io::Result<()> {
+    let mut header = vec!["parameters".to_string(), "runs".into(), "successes".into()];
+    for metric in ["collection_time", "coverage", "stuck_events"] {
+        header.push(format!("{}_count", metric));
+        for stat in ["mean", "median", "p10", "p90"] {
+            header.push(format!("{}_{}", metric, stat));
+        }
+    }
+    writeln!(out, "{}", header.join(","))?;
+
+    for summary in summaries {
+        let mut row = vec![summary.parameters.label(), summary.runs.to_string(), summary.successes.to_string()];
+        for distribution in [summary.collection_time, summary.coverage, summary.stuck_events] {
+            row.push(distribution.count.to_string());
+            for value in [distribution.mean, distribution.median, distribution.p10, distribution.p90] {
+                row.push(format!("{:.3}", value));
+            }
+        }
+        writeln!(out, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+pub fn write_runs_csv(results: &[RunResult], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "parameters,seed,success,elapsed,collection_time,coverage,stuck_events,units_collected")?;
+    for result in results {
+        writeln!(
+            out,
+            "{},{},{},{:.3},{},{:.3},{},{}",
+            result.parameters.label(),
+            result.seed,
+            result.success.map_or("".into(), |success| success.to_string()),
+            result.elapsed,
+            result.collection_time.map_or("".into(), |time| format!("{:.3}", time)),
+            result.coverage,
+            result.stuck_events,
+            result.units_collected
+        )?;
+    }
+    Ok(())
+}
+
+// "1..20" (borne incluse) ou "1,4,7"
+pub fn parse_seeds(text: &str) -> Result<Vec<u32>, String> {
+    if let Some((start, end)) = text.split_once("..") {
+        let start: u32 = start.trim().parse().map_err(|_| format!("graine invalide: {}", start))?;
+        let end: u32 = end.trim().parse().map_err(|_| format!("graine invalide: {}", end))?;
+        if start > end {
+            return Err(format!("intervalle vide: {}", text));
+        }
+        return Ok((start..=end).collect());
+    }
+    text.split(',')
+        .map(|seed| seed.trim().parse().map_err(|_| format!("graine invalide: {}", seed)))
+        .collect()
+}
@@ -0,0 +1,729 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::clock::SimulationClock;
+use crate::comms::{CommsConfig, Network, Radio};
+use crate::daylight::{Battery, DaylightConfig, CHARGING_DISTANCE};
+use crate::manual::ManuallyControlled;
+use crate::motion::Kinematics;
+use crate::pathfinding::PlannedPath;
+use crate::reliability::Reliability;
+use crate::robots::{
+    drive_towards, load_cargo, nearest_base, unload_cargo, wander, Base, Cargo, CollectionState, Deposit,
+    DiscoveredResource, ExplorationMode, Explorer, ExplorerState, GameMap, Miner, Resource, RobotBody,
+    SimulationRng, Station,
+};
+use crate::scenario::{RobotKind, ScenarioError, Timers};
+use crate::state::{set_state, RobotState, StateTransition, ARRIVAL_DISTANCE};
+use crate::stats::SimulationStats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    Running,
+}
+
+impl From<bool> for Status {
+    fn from(value: bool) -> Self {
+        if value {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    HasCargo,
+    AtBase,
+    Returning,
+    Collecting,
+    Charging,
+    BatteryLow,
+    BatteryEmpty,
+    RepairRequested,
+    ReportOverdue,
+    ExplorationPaused,
+    // Vrai si le tableau noir contient la clé
+    Known(String),
+    Flag(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    // Se rend à la position rangée sous cette clé du tableau noir
+    MoveTo(String),
+    ReturnToBase,
+    Collect,
+    Unload,
+    // Tour complet sur place pour balayer les environs avec le capteur
+    Scan,
+    Wander,
+    Charge,
+    Wait,
+    // Arrêt sans changer d'état
+    Halt,
+    SetFlag(String, bool),
+    Forget(String),
+}
+
+// Arbre réévalué depuis la racine à chaque pas: la mémoire d'une action en cours
+// est portée par l'état du robot (progression de collecte) ou par le tableau noir
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BehaviorNode {
+    Sequence(Vec<BehaviorNode>),
+    Selector(Vec<BehaviorNode>),
+    // Réussit dès que `success_threshold` enfants réussissent, échoue quand ce n'est plus possible
+    Parallel { success_threshold: usize, children: Vec<BehaviorNode> },
+    Inverter(Box<BehaviorNode>),
+    AlwaysSucceed(Box<BehaviorNode>),
+    // Refuse de relancer l'enfant moins de `seconds` après sa dernière réussite
+    Cooldown { key: String, seconds: f32, child: Box<BehaviorNode> },
+    Condition(Condition),
+    Action(Action),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlackboardValue {
+    Flag(bool),
+    Number(f32),
+    Position(Vec2),
+}
+
+// Mémoire propre à chaque robot, partagée par les nœuds de son arbre
+#[derive(Debug, Clone, Default)]
+pub struct Blackboard {
+    pub time: f32,
+    values: HashMap<String, BlackboardValue>,
+}
+
+impl Blackboard {
+    pub fn set(&mut self, key: &str, value: BlackboardValue) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn flag(&self, key: &str) -> bool {
+        matches!(self.values.get(key), Some(BlackboardValue::Flag(true)))
+    }
+
+    pub fn number(&self, key: &str) -> Option<f32> {
+        match self.values.get(key) {
+            Some(BlackboardValue::Number(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn position(&self, key: &str) -> Option<Vec2> {
+        match self.values.get(key) {
+            Some(BlackboardValue::Position(position)) => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+// Ce que les feuilles de l'arbre demandent au robot; les conditions `Known` et `Flag`
+// et les actions sur le tableau noir sont traitées par l'arbre lui-même
+pub trait BehaviorAgent {
+    fn check(&mut self, condition: &Condition, blackboard: &Blackboard) -> bool;
+    fn perform(&mut self, action: &Action, blackboard: &mut Blackboard) -> Status;
+}
+
+impl BehaviorNode {
+    pub fn tick(&self, agent: &mut impl BehaviorAgent, blackboard: &mut Blackboard) -> Status {
+        match self {
+            BehaviorNode::Sequence(children) => {
+                for child in children {
+                    match child.tick(agent, blackboard) {
+                        Status::Success => continue,
+                        other => return other,
+                    }
+                }
+                Status::Success
+            }
+            BehaviorNode::Selector(children) => {
+                for child in children {
+                    match child.tick(agent, blackboard) {
+                        Status::Failure => continue,
+                        other => return other,
+                    }
+                }
+                Status::Failure
+            }
+            BehaviorNode::Parallel { success_threshold, children } => {
+                let mut successes = 0;
+                let mut failures = 0;
+                for child in children {
+                    match child.tick(agent, blackboard) {
+                        Status::Success => successes += 1,
+                        Status::Failure => failures += 1,
+                        Status::Running => {}
+                    }
+                }
+                if successes >= *success_threshold {
+                    Status::Success
+                } else if failures > children.len() - success_threshold {
+                    Status::Failure
+                } else {
+                    Status::Running
+                }
+            }
+            BehaviorNode::Inverter(child) => match child.tick(agent, blackboard) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            BehaviorNode::AlwaysSucceed(child) => match child.tick(agent, blackboard) {
+                Status::Running => Status::Running,
+                _ => Status::Success,
+            },
+            BehaviorNode::Cooldown { key, seconds, child } => {
+                if blackboard.number(key).is_some_and(|last| blackboard.time - last < *seconds) {
+                    return Status::Failure;
+                }
+                let status = child.tick(agent, blackboard);
+                if status == Status::Success {
+                    let now = blackboard.time;
+                    blackboard.set(key, BlackboardValue::Number(now));
+                }
+                status
+            }
+            BehaviorNode::Condition(Condition::Known(key)) => blackboard.contains(key).into(),
+            BehaviorNode::Condition(Condition::Flag(key)) => blackboard.flag(key).into(),
+            BehaviorNode::Condition(condition) => agent.check(condition, blackboard).into(),
+            BehaviorNode::Action(Action::SetFlag(key, value)) => {
+                blackboard.set(key, BlackboardValue::Flag(*value));
+                Status::Success
+            }
+            BehaviorNode::Action(Action::Forget(key)) => {
+                blackboard.remove(key);
+                Status::Success
+            }
+            BehaviorNode::Action(action) => agent.perform(action, blackboard),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            BehaviorNode::Sequence(children) | BehaviorNode::Selector(children) => {
+                if children.is_empty() {
+                    return Err("séquence ou sélecteur sans enfant".into());
+                }
+                children.iter().try_for_each(BehaviorNode::validate)
+            }
+            BehaviorNode::Parallel { success_threshold, children } => {
+                if *success_threshold == 0 || *success_threshold > children.len() {
+                    return Err(format!(
+                        "parallèle: seuil {} pour {} enfants",
+                        success_threshold,
+                        children.len()
+                    ));
+                }
+                children.iter().try_for_each(BehaviorNode::validate)
+            }
+            BehaviorNode::Cooldown { seconds, child, .. } => {
+                if *seconds <= 0.0 {
+                    return Err("délai de cooldown non positif".into());
+                }
+                child.validate()
+            }
+            BehaviorNode::Inverter(child) | BehaviorNode::AlwaysSucceed(child) => child.validate(),
+            BehaviorNode::Condition(_) | BehaviorNode::Action(_) => Ok(()),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<BehaviorNode, ScenarioError> {
+        let text = std::fs::read_to_string(path).map_err(ScenarioError::Io)?;
+        BehaviorNode::from_ron(&text)
+    }
+
+    pub fn from_ron(text: &str) -> Result<BehaviorNode, ScenarioError> {
+        let tree: BehaviorNode = ron::from_str(text).map_err(ScenarioError::Parse)?;
+        tree.validate().map_err(ScenarioError::Invalid)?;
+        Ok(tree)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ScenarioError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map_err(ScenarioError::Serialize)
+    }
+
+    // Comportement historique de move_explorer
+    pub fn explorer() -> BehaviorNode {
+        use self::Action as A;
+        use self::BehaviorNode::{Action, Condition, Selector, Sequence};
+        use self::Condition as C;
+
+        Selector(vec![
+            Sequence(vec![Condition(C::BatteryEmpty), Action(A::Halt)]),
+            Sequence(vec![Condition(C::Charging), Action(A::Charge)]),
+            Sequence(vec![Condition(C::BatteryLow), Condition(C::AtBase), Action(A::Charge)]),
+            Sequence(vec![
+                Selector(vec![
+                    Condition(C::RepairRequested),
+                    Condition(C::ReportOverdue),
+                    Condition(C::BatteryLow),
+                ]),
+                Action(A::ReturnToBase),
+            ]),
+            Sequence(vec![Condition(C::ExplorationPaused), Action(A::Wait)]),
+            Action(A::Wander),
+        ])
+    }
+
+    // Comportement historique de move_miners
+    pub fn miner() -> BehaviorNode {
+        use self::Action as A;
+        use self::BehaviorNode::{Action, Condition, Inverter, Selector, Sequence};
+        use self::Condition as C;
+
+        Selector(vec![
+            Sequence(vec![Condition(C::BatteryEmpty), Action(A::Halt)]),
+            Sequence(vec![Condition(C::RepairRequested), Action(A::ReturnToBase)]),
+            Sequence(vec![
+                Selector(vec![Condition(C::HasCargo), Condition(C::Returning)]),
+                Action(A::ReturnToBase),
+                Action(A::Unload),
+            ]),
+            Sequence(vec![
+                Condition(C::Known(DEPOSIT_KEY.into())),
+                Selector(vec![Condition(C::Collecting), Action(A::MoveTo(DEPOSIT_KEY.into()))]),
+                Action(A::Collect),
+            ]),
+            Sequence(vec![Inverter(Box::new(Condition(C::AtBase))), Action(A::ReturnToBase)]),
+            Action(A::Wait),
+        ])
+    }
+}
+
+// Clés publiées par la simulation dans le tableau noir avant chaque pas
+pub const BASE_KEY: &str = "base";
+pub const DEPOSIT_KEY: &str = "deposit";
+const SCAN_KEY: &str = "scan";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TreeSource {
+    // Arbre reproduisant la stratégie codée en dur du type de robot
+    Default,
+    File(String),
+    Inline(BehaviorNode),
+}
+
+impl TreeSource {
+    pub fn resolve(&self, kind: RobotKind) -> Result<BehaviorNode, ScenarioError> {
+        match self {
+            TreeSource::Default => Ok(match kind {
+                RobotKind::Explorer => BehaviorNode::explorer(),
+                RobotKind::Miner => BehaviorNode::miner(),
+            }),
+            TreeSource::File(path) => BehaviorNode::load(path),
+            TreeSource::Inline(tree) => {
+                tree.validate().map_err(ScenarioError::Invalid)?;
+                Ok(tree.clone())
+            }
+        }
+    }
+}
+
+// Arbre de comportement par type de robot; sans arbre, le robot garde sa stratégie codée en dur
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BehaviorConfig {
+    pub explorer: Option<TreeSource>,
+    pub miner: Option<TreeSource>,
+}
+
+impl BehaviorConfig {
+    pub fn load(&self) -> Result<BehaviorTrees, ScenarioError> {
+        let resolve = |source: &Option<TreeSource>, kind| -> Result<Option<Arc<BehaviorNode>>, ScenarioError> {
+            source.as_ref().map(|source| source.resolve(kind).map(Arc::new)).transpose()
+        };
+        Ok(BehaviorTrees {
+            explorer: resolve(&self.explorer, RobotKind::Explorer)?,
+            miner: resolve(&self.miner, RobotKind::Miner)?,
+        })
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BehaviorTrees {
+    pub explorer: Option<Arc<BehaviorNode>>,
+    pub miner: Option<Arc<BehaviorNode>>,
+}
+
+#[derive(Component, Debug)]
+pub struct BehaviorTree {
+    pub root: Arc<BehaviorNode>,
+    pub blackboard: Blackboard,
+    pub status: Status,
+}
+
+impl BehaviorTree {
+    pub fn new(root: Arc<BehaviorNode>) -> Self {
+        Self {
+            root,
+            blackboard: Blackboard::default(),
+            status: Status::Running,
+        }
+    }
+}
+
+// Donne leur arbre aux robots du scénario comme à ceux produits en cours de partie
+#[allow(clippy::type_complexity)]
+pub fn attach_behavior_trees(
+    mut commands: Commands,
+    trees: Res<BehaviorTrees>,
+    robots_query: Query<(Entity, Option<&Explorer>, Option<&Miner>), (With<RobotBody>, Without<BehaviorTree>)>,
+) {
+    if trees.explorer.is_none() && trees.miner.is_none() {
+        return;
+    }
+    for (entity, explorer, miner) in robots_query.iter() {
+        let tree = match (explorer, miner) {
+            (Some(_), _) => &trees.explorer,
+            (_, Some(_)) => &trees.miner,
+            _ => &None,
+        };
+        if let Some(root) = tree {
+            commands.entity(entity).insert(BehaviorTree::new(root.clone()));
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct BehaviorWorld<'w, 's> {
+    pub(crate) commands: Commands<'w, 's>,
+    pub(crate) bases_query: Query<'w, 's, &'static Transform, (With<Base>, Without<RobotBody>)>,
+    pub(crate) deposits_query: Query<'w, 's, (&'static Resource, &'static mut Deposit)>,
+    pub(crate) discovered_resource: ResMut<'w, DiscoveredResource>,
+    pub(crate) collection_state: ResMut<'w, CollectionState>,
+    pub(crate) station: ResMut<'w, Station>,
+    pub(crate) stats: ResMut<'w, SimulationStats>,
+    pub(crate) transitions: EventWriter<'w, StateTransition>,
+    pub(crate) rng: ResMut<'w, SimulationRng>,
+    pub(crate) clock: Res<'w, SimulationClock>,
+    pub(crate) timers: Res<'w, Timers>,
+    pub(crate) game_map: Res<'w, GameMap>,
+    pub(crate) daylight: Res<'w, DaylightConfig>,
+    pub(crate) comms: Res<'w, CommsConfig>,
+    pub(crate) network: Res<'w, Network>,
+    pub(crate) exploration_mode: Res<'w, ExplorationMode>,
+}
+
+pub(crate) struct RobotAgent<'a, 'w, 's> {
+    pub(crate) entity: Entity,
+    pub(crate) transform: &'a mut Transform,
+    pub(crate) state: &'a mut RobotState,
+    pub(crate) kinematics: &'a mut Kinematics,
+    pub(crate) path: &'a mut PlannedPath,
+    pub(crate) radius: f32,
+    pub(crate) cargo: Option<&'a mut Cargo>,
+    pub(crate) explorer_state: Option<&'a mut ExplorerState>,
+    pub(crate) battery: Option<&'a Battery>,
+    pub(crate) reliability: Option<&'a Reliability>,
+    pub(crate) radio: Option<&'a Radio>,
+    pub(crate) base: Vec2,
+    pub(crate) paused: bool,
+    pub(crate) world: &'a mut BehaviorWorld<'w, 's>,
+}
+
+impl BehaviorWorld<'_, '_> {
+    // En exploration sérialisée, les explorateurs attendent que le gisement trouvé soit vidé
+    pub(crate) fn exploration_paused(&self, miners_busy: bool) -> bool {
+        *self.exploration_mode == ExplorationMode::Serialized
+            && (self.discovered_resource.position.is_some() || miners_busy)
+    }
+}
+
+impl RobotAgent<'_, '_, '_> {
+    pub(crate) fn position(&self) -> Vec2 {
+        self.transform.translation.truncate()
+    }
+
+    fn set_state(&mut self, next: RobotState) {
+        set_state(self.entity, self.state, next, &mut self.world.transitions);
+    }
+
+    fn stop(&mut self) {
+        self.kinematics.speed = 0.0;
+    }
+
+    fn drive_to(&mut self, destination: Vec2) {
+        let delta = self.world.clock.delta_seconds();
+        drive_towards(self.transform, self.kinematics, self.path, self.radius, destination, delta, &self.world.game_map);
+    }
+}
+
+impl BehaviorAgent for RobotAgent<'_, '_, '_> {
+    fn check(&mut self, condition: &Condition, _blackboard: &Blackboard) -> bool {
+        let distance_to_base = self.position().distance(self.base);
+        match condition {
+            Condition::HasCargo => self.cargo.as_ref().is_some_and(|cargo| cargo.amount > 0),
+            Condition::AtBase => distance_to_base < CHARGING_DISTANCE,
+            Condition::Returning => *self.state == RobotState::Returning,
+            Condition::Collecting => matches!(*self.state, RobotState::Collecting { .. }),
+            Condition::Charging => *self.state == RobotState::Charging,
+            // Une fois en route, le robot rentre jusqu'au bout au lieu d'osciller autour du seuil
+            Condition::BatteryLow => self.battery.is_some_and(|battery| {
+                battery.needs_recharge(distance_to_base, &self.world.daylight)
+                    || (*self.state == RobotState::Returning && !battery.is_full())
+            }),
+            Condition::BatteryEmpty => self.battery.is_some_and(Battery::is_empty),
+            Condition::RepairRequested => self.reliability.is_some_and(|reliability| reliability.repair_requested),
+            Condition::ReportOverdue => self.world.comms.report_overdue(self.radio, &self.world.network, self.world.stats.elapsed),
+            Condition::ExplorationPaused => self.paused,
+            Condition::Known(_) | Condition::Flag(_) => false,
+        }
+    }
+
+    fn perform(&mut self, action: &Action, blackboard: &mut Blackboard) -> Status {
+        let position = self.position();
+        let delta = self.world.clock.delta_seconds();
+        match action {
+            Action::MoveTo(key) => {
+                let Some(destination) = blackboard.position(key) else {
+                    return Status::Failure;
+                };
+                if position.distance(destination) < ARRIVAL_DISTANCE {
+                    self.stop();
+                    return Status::Success;
+                }
+                if *self.state != RobotState::MovingTo(destination) {
+                    println!("🚀 Robot {:?} en route vers {:?}", self.entity, destination);
+                }
+                self.set_state(RobotState::MovingTo(destination));
+                self.drive_to(destination);
+                Status::Running
+            }
+            Action::ReturnToBase => {
+                self.set_state(RobotState::Returning);
+                if position.distance(self.base) < ARRIVAL_DISTANCE {
+                    self.stop();
+                    return Status::Success;
+                }
+                self.drive_to(self.base);
+                Status::Running
+            }
+            Action::Collect => {
+                let world = &mut *self.world;
+                let (Some(cargo), Some(target), Some(deposit_entity)) = (
+                    self.cargo.as_deref_mut(),
+                    world.collection_state.position,
+                    world.collection_state.resource_entity,
+                ) else {
+                    return Status::Failure;
+                };
+                // La collecte commence au gisement et continue même si un autre robot pousse le mineur
+                let collecting = matches!(*self.state, RobotState::Collecting { .. });
+                if !collecting && position.distance(target) >= ARRIVAL_DISTANCE {
+                    return Status::Failure;
+                }
+                let Ok((resource, mut deposit)) = world.deposits_query.get_mut(deposit_entity) else {
+                    return Status::Failure;
+                };
+                if deposit.is_exhausted() {
+                    return Status::Failure;
+                }
+
+                self.kinematics.speed = 0.0;
+                let progress = match *self.state {
+                    RobotState::Collecting { progress } => progress,
+                    _ => {
+                        println!("⏱️ Début de la collecte pour le mineur {:?}", self.entity);
+                        0.0
+                    }
+                } + delta / world.timers.collection_time;
+                if progress < 1.0 {
+                    set_state(self.entity, self.state, RobotState::Collecting { progress }, &mut world.transitions);
+                    return Status::Running;
+                }
+
+                let exhausted = load_cargo(
+                    &mut world.commands,
+                    self.entity,
+                    cargo,
+                    deposit_entity,
+                    *resource,
+                    &mut deposit,
+                    &mut world.station,
+                    &mut world.stats,
+                );
+                if exhausted {
+                    world.collection_state.release(&mut world.discovered_resource);
+                }
+                set_state(self.entity, self.state, RobotState::Collecting { progress: 0.0 }, &mut world.transitions);
+                Status::Success
+            }
+            Action::Unload => {
+                if position.distance(self.base) >= CHARGING_DISTANCE {
+                    return Status::Failure;
+                }
+                let Some(cargo) = self.cargo.as_deref_mut() else {
+                    return Status::Failure;
+                };
+                self.kinematics.speed = 0.0;
+                unload_cargo(self.entity, cargo, &mut self.world.station, &mut self.world.stats);
+                self.set_state(RobotState::Unloading);
+                Status::Success
+            }
+            Action::Scan => {
+                self.stop();
+                self.set_state(RobotState::Idle);
+                let step = self.kinematics.config.max_turn_rate * delta;
+                let turned = blackboard.number(SCAN_KEY).unwrap_or(0.0) + step;
+                self.kinematics.heading += step;
+                self.transform.rotation = self.kinematics.rotation();
+                if turned >= TAU {
+                    blackboard.remove(SCAN_KEY);
+                    return Status::Success;
+                }
+                blackboard.set(SCAN_KEY, BlackboardValue::Number(turned));
+                Status::Running
+            }
+            Action::Wander => {
+                let Some(explorer_state) = self.explorer_state.as_deref_mut() else {
+                    return Status::Failure;
+                };
+                if *self.state == RobotState::Returning {
+                    self.path.clear();
+                    set_state(self.entity, self.state, RobotState::Idle, &mut self.world.transitions);
+                }
+                set_state(self.entity, self.state, RobotState::Exploring, &mut self.world.transitions);
+                wander(
+                    self.transform,
+                    explorer_state,
+                    self.kinematics,
+                    self.radius,
+                    &mut self.world.rng.0,
+                    self.world.timers.explorer_turn_interval,
+                    delta,
+                    &self.world.game_map,
+                );
+                Status::Running
+            }
+            Action::Charge => {
+                self.stop();
+                if self.battery.is_none() || self.battery.is_some_and(Battery::is_full) {
+                    if *self.state == RobotState::Charging {
+                        println!("🔋 Robot {:?} rechargé", self.entity);
+                        self.set_state(RobotState::Idle);
+                    }
+                    return Status::Success;
+                }
+                if *self.state != RobotState::Charging {
+                    self.path.clear();
+                    println!("🔌 Robot {:?} en charge à la base", self.entity);
+                }
+                self.set_state(RobotState::Charging);
+                Status::Running
+            }
+            Action::Wait => {
+                self.stop();
+                self.set_state(RobotState::Idle);
+                Status::Success
+            }
+            Action::Halt => {
+                self.stop();
+                Status::Success
+            }
+            Action::SetFlag(..) | Action::Forget(_) => Status::Failure,
+        }
+    }
+}
+
+// Les robots munis d'un arbre passent par les mêmes fonctions de déplacement et de collecte
+// que les stratégies codées en dur; à placer après move_miners, qui met à jour la cible des mineurs
+#[allow(clippy::type_complexity)]
+pub fn run_behavior_trees(
+    mut robots_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut RobotState,
+        &mut Kinematics,
+        &mut PlannedPath,
+        &RobotBody,
+        Option<&mut BehaviorTree>,
+        Option<&mut Cargo>,
+        Option<&mut ExplorerState>,
+        Option<&Battery>,
+        Option<&Reliability>,
+        Option<&Radio>,
+        Option<&Miner>,
+        Option<&ManuallyControlled>,
+    )>,
+    mut world: BehaviorWorld,
+) {
+    let bases: Vec<Vec2> = world.bases_query.iter().map(|transform| transform.translation.truncate()).collect();
+    let paused = world.exploration_paused(
+        robots_query
+            .iter()
+            .any(|(_, _, state, .., miner, _)| miner.is_some() && state.is_busy()),
+    );
+
+    for (
+        entity,
+        mut transform,
+        mut state,
+        mut kinematics,
+        mut path,
+        body,
+        tree,
+        mut cargo,
+        mut explorer_state,
+        battery,
+        reliability,
+        radio,
+        _,
+        manual,
+    ) in robots_query.iter_mut()
+    {
+        let Some(mut tree) = tree else {
+            continue;
+        };
+        if manual.is_some() || matches!(*state, RobotState::Stuck | RobotState::Repairing { .. }) {
+            continue;
+        }
+
+        let base = nearest_base(transform.translation.truncate(), &bases);
+        let tree = &mut *tree;
+        tree.blackboard.time = world.stats.elapsed;
+        tree.blackboard.set(BASE_KEY, BlackboardValue::Position(base));
+        match world.collection_state.position {
+            Some(target) if world.collection_state.deposit_available(&world.deposits_query) => {
+                tree.blackboard.set(DEPOSIT_KEY, BlackboardValue::Position(target));
+            }
+            _ => tree.blackboard.remove(DEPOSIT_KEY),
+        }
+
+        let mut agent = RobotAgent {
+            entity,
+            transform: &mut transform,
+            state: &mut state,
+            kinematics: &mut kinematics,
+            path: &mut path,
+            radius: body.radius,
+            cargo: cargo.as_deref_mut(),
+            explorer_state: explorer_state.as_deref_mut(),
+            battery,
+            reliability,
+            radio,
+            base,
+            paused,
+            world: &mut world,
+        };
+        tree.status = tree.root.tick(&mut agent, &mut tree.blackboard);
+    }
+}
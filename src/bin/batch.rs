@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::process::exit;
+use std::thread;
+
+use simulation_robots::batch::{
+    parameter_grid, parse_seeds, run_batch, summarize, write_runs_csv, write_summary_csv, BatchConfig,
+};
+use simulation_robots::robots::ExplorationMode;
+use simulation_robots::scenario::Scenario;
+
+const USAGE: &str = "Usage: batch [scenario.ron] [--seeds 1..20] [--modes serialized,concurrent] \
+[--miners 2,3] [--explorers 1,2] [--units 10] [--max-time 600] [--threads N] \
+[--output resume.csv] [--runs executions.csv]";
+
+fn fail(message: String) -> ! {
+    eprintln!("❌ {}", message);
+    eprintln!("{}", USAGE);
+    exit(1);
+}
+
+fn parse_list<T>(text: &str, parse: impl Fn(&str) -> Option<T>) -> Vec<T> {
+    text.split(',')
+        .map(|value| parse(value.trim()).unwrap_or_else(|| fail(format!("valeur invalide: {}", value))))
+        .collect()
+}
+
+fn main() {
+    let mut scenario = Scenario::default();
+    let mut seeds: Vec<u32> = (1..=10).collect();
+    let mut modes = Vec::new();
+    let mut miners = Vec::new();
+    let mut explorers = Vec::new();
+    let mut units = 10;
+    let mut max_time = 600.0;
+    let mut threads = thread::available_parallelism().map_or(1, |count| count.get());
+    let mut output = "batch_summary.csv".to_string();
+    let mut runs_output = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            scenario = Scenario::load(&arg).unwrap_or_else(|error| fail(format!("{}: {}", arg, error)));
+            continue;
+        }
+        let value = args.next().unwrap_or_else(|| fail(format!("valeur manquante pour {}", arg)));
+        match arg.as_str() {
+            "--seeds" => seeds = parse_seeds(&value).unwrap_or_else(|error| fail(error)),
+            "--modes" => {
+                modes = parse_list(&value, |mode| match mode {
+                    "serialized" => Some(ExplorationMode::Serialized),
+                    "concurrent" => Some(ExplorationMode::Concurrent),
+                    _ => None,
+                })
+            }
+            "--miners" => miners = parse_list(&value, |count| count.parse().ok()),
+            "--explorers" => explorers = parse_list(&value, |count| count.parse().ok()),
+            "--units" => units = value.parse().unwrap_or_else(|_| fail(format!("--units invalide: {}", value))),
+            "--max-time" => max_time = value.parse().unwrap_or_else(|_| fail(format!("--max-time invalide: {}", value))),
+            "--threads" => threads = value.parse().unwrap_or_else(|_| fail(format!("--threads invalide: {}", value))),
+            "--output" => output = value,
+            "--runs" => runs_output = Some(value),
+            _ => fail(format!("option inconnue: {}", arg)),
+        }
+    }
+
+    let parameters = parameter_grid(&modes, &miners, &explorers);
+    for set in &parameters {
+        if let Err(error) = set.apply(&scenario).validate() {
+            fail(format!("{}: {}", set.label(), error));
+        }
+    }
+
+    eprintln!(
+        "🧪 {}: {} combinaisons x {} graines sur {} fils",
+        scenario.name,
+        parameters.len(),
+        seeds.len(),
+        threads
+    );
+    let config = BatchConfig { scenario, seeds, parameters, units, max_time, threads };
+    let results = run_batch(&config);
+    let summaries = summarize(&config.parameters, &results);
+
+    let file = File::create(&output).unwrap_or_else(|error| fail(format!("{}: {}", output, error)));
+    write_summary_csv(&summaries, file).unwrap_or_else(|error| fail(format!("{}: {}", output, error)));
+    eprintln!("📄 Résumé écrit dans {}", output);
+
+    if let Some(path) = runs_output {
+        let file = File::create(&path).unwrap_or_else(|error| fail(format!("{}: {}", path, error)));
+        write_runs_csv(&results, file).unwrap_or_else(|error| fail(format!("{}: {}", path, error)));
+        eprintln!("📄 Exécutions écrites dans {}", path);
+    }
+}
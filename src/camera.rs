@@ -0,0 +1,348 @@
+use bevy::prelude::*;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use bevy::window::PrimaryWindow;
+
+use crate::manual::ManualControl;
+use crate::robots::{Base, Explorer, ExploredZones, GameMap, Miner};
+use crate::spatial::{SpatialIndex, SpatialKind};
+
+#[derive(Component)]
+pub struct MainCamera;
+
+#[derive(Component)]
+struct Minimap;
+
+#[derive(Component)]
+struct MinimapLabel;
+
+#[derive(Resource)]
+pub struct CameraSettings {
+    pub pan_speed: f32,
+    pub zoom_step: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub minimap_width: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            pan_speed: 600.0,
+            zoom_step: 0.1,
+            min_zoom: 0.25,
+            max_zoom: 6.0,
+            minimap_width: 200.0,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SelectedRobot(pub Option<Entity>);
+
+#[derive(Resource, Default)]
+pub struct CameraFollow {
+    pub active: bool,
+}
+
+#[derive(Resource)]
+struct MinimapImage(Handle<Image>);
+
+pub struct CameraControlPlugin;
+
+impl Plugin for CameraControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraSettings>()
+            .init_resource::<SelectedRobot>()
+            .init_resource::<CameraFollow>()
+            .add_systems(Startup, (spawn_camera, spawn_minimap))
+            .add_systems(
+                Update,
+                (
+                    select_robot_on_click,
+                    toggle_follow,
+                    pan_camera,
+                    zoom_camera,
+                    follow_selected_robot,
+                    clamp_camera_to_map,
+                    update_minimap,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Camera2dBundle::default(), MainCamera));
+}
+
+pub fn cursor_world_position(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Option<Vec2> {
+    let window = windows.get_single().ok()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    let cursor = window.cursor_position()?;
+    camera.viewport_to_world_2d(camera_transform, cursor)
+}
+
+fn select_robot_on_click(
+    mouse: Res<Input<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    spatial_index: Res<SpatialIndex>,
+    mut selected: ResMut<SelectedRobot>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(cursor) = cursor_world_position(&windows, &camera_query) else {
+        return;
+    };
+
+    if let Some((entity, _, _)) = spatial_index.nearest(cursor, 20.0, SpatialKind::Robot) {
+        println!("🖱️ Robot {:?} sélectionné", entity);
+        selected.0 = Some(entity);
+    }
+}
+
+fn toggle_follow(
+    keyboard: Res<Input<KeyCode>>,
+    selected: Res<SelectedRobot>,
+    mut follow: ResMut<CameraFollow>,
+) {
+    if keyboard.just_pressed(KeyCode::F) && selected.0.is_some() {
+        follow.active = !follow.active;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        follow.active = false;
+    }
+}
+
+fn pan_camera(
+    keyboard: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    mut follow: ResMut<CameraFollow>,
+    manual: Res<ManualControl>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    // Les flèches pilotent le robot contrôlé à la main
+    if manual.entity.is_some() {
+        return;
+    }
+
+    let mut direction = Vec2::ZERO;
+    if keyboard.pressed(KeyCode::Left) {
+        direction.x -= 1.0;
+    }
+    if keyboard.pressed(KeyCode::Right) {
+        direction.x += 1.0;
+    }
+    if keyboard.pressed(KeyCode::Up) {
+        direction.y += 1.0;
+    }
+    if keyboard.pressed(KeyCode::Down) {
+        direction.y -= 1.0;
+    }
+
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    follow.active = false;
+
+    for (mut transform, projection) in camera_query.iter_mut() {
+        let step = direction.normalize() * settings.pan_speed * projection.scale * time.delta_seconds();
+        transform.translation += step.extend(0.0);
+    }
+}
+
+fn zoom_camera(
+    mut wheel_events: EventReader<MouseWheel>,
+    settings: Res<CameraSettings>,
+    mut camera_query: Query<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    let mut scroll = 0.0;
+    for event in wheel_events.iter() {
+        scroll += match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 40.0,
+        };
+    }
+
+    if scroll == 0.0 {
+        return;
+    }
+
+    for mut projection in camera_query.iter_mut() {
+        let factor = 1.0 - scroll * settings.zoom_step;
+        projection.scale = (projection.scale * factor).clamp(settings.min_zoom, settings.max_zoom);
+    }
+}
+
+fn follow_selected_robot(
+    follow: Res<CameraFollow>,
+    selected: Res<SelectedRobot>,
+    robots_query: Query<&Transform, Without<MainCamera>>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    if !follow.active {
+        return;
+    }
+
+    let Some(target) = selected.0.and_then(|entity| robots_query.get(entity).ok()) else {
+        return;
+    };
+
+    for mut transform in camera_query.iter_mut() {
+        transform.translation.x = target.translation.x;
+        transform.translation.y = target.translation.y;
+    }
+}
+
+fn clamp_camera_to_map(
+    game_map: Res<GameMap>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    for (mut transform, projection) in camera_query.iter_mut() {
+        let half_view = Vec2::new(window.width(), window.height()) * projection.scale / 2.0;
+        let half_map = game_map.size / 2.0;
+
+        let limit = (half_map - half_view).max(Vec2::ZERO);
+        transform.translation.x = transform.translation.x.clamp(-limit.x, limit.x);
+        transform.translation.y = transform.translation.y.clamp(-limit.y, limit.y);
+    }
+}
+
+fn spawn_minimap(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    game_map: Res<GameMap>,
+    settings: Res<CameraSettings>,
+) {
+    let cols = game_map.obstacles[0].len() as u32;
+    let rows = game_map.obstacles.len() as u32;
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: cols,
+            height: rows,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::nearest();
+    let handle = images.add(image);
+
+    let width = settings.minimap_width;
+    let height = width * rows as f32 / cols as f32;
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                ImageBundle {
+                    style: Style {
+                        width: Val::Px(width),
+                        height: Val::Px(height),
+                        ..default()
+                    },
+                    image: UiImage::new(handle.clone()),
+                    ..default()
+                },
+                Minimap,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 14.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                MinimapLabel,
+            ));
+        });
+
+    commands.insert_resource(MinimapImage(handle));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_minimap(
+    minimap: Res<MinimapImage>,
+    mut images: ResMut<Assets<Image>>,
+    game_map: Res<GameMap>,
+    explored_zones: Res<ExploredZones>,
+    explorer_query: Query<&Transform, With<Explorer>>,
+    miners_query: Query<&Transform, With<Miner>>,
+    base_query: Query<&Transform, With<Base>>,
+    mut label_query: Query<&mut Text, With<MinimapLabel>>,
+) {
+    let Some(image) = images.get_mut(&minimap.0) else {
+        return;
+    };
+
+    let cols = game_map.obstacles[0].len();
+    let rows = game_map.obstacles.len();
+
+    let mut set_pixel = |x: usize, y: usize, color: [u8; 4]| {
+        // L'image a son origine en haut à gauche, la grille en bas à gauche
+        let index = ((rows - 1 - y) * cols + x) * 4;
+        image.data[index..index + 4].copy_from_slice(&color);
+    };
+
+    for y in 0..rows {
+        for x in 0..cols {
+            let color = match (explored_zones.grid[y][x], game_map.obstacles[y][x]) {
+                (false, _) => [0, 0, 0, 255],
+                (true, true) => [110, 110, 110, 255],
+                (true, false) => [35, 60, 35, 255],
+            };
+            set_pixel(x, y, color);
+        }
+    }
+
+    let mut draw_entity = |pos: Vec3, color: [u8; 4]| {
+        let x = ((pos.x + game_map.size.x / 2.0) / game_map.cell_size) as i32;
+        let y = ((pos.y + game_map.size.y / 2.0) / game_map.cell_size) as i32;
+        if x >= 0 && x < cols as i32 && y >= 0 && y < rows as i32 {
+            set_pixel(x as usize, y as usize, color);
+        }
+    };
+
+    for transform in base_query.iter() {
+        draw_entity(transform.translation, [60, 60, 255, 255]);
+    }
+    for transform in miners_query.iter() {
+        draw_entity(transform.translation, [255, 128, 0, 255]);
+    }
+    for transform in explorer_query.iter() {
+        draw_entity(transform.translation, [0, 255, 0, 255]);
+    }
+
+    for mut text in label_query.iter_mut() {
+        text.sections[0].value = format!("Exploré: {:.1}%", explored_zones.explored_ratio() * 100.0);
+    }
+}
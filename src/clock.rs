@@ -0,0 +1,161 @@
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+
+// Ordonnancement qui contient tous les systèmes de simulation, exécuté une fois par pas fixe
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationTick;
+
+pub const MIN_TIME_SCALE: f32 = 0.25;
+pub const MAX_TIME_SCALE: f32 = 16.0;
+const MAX_TICKS_PER_FRAME: u32 = 64;
+
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationClock {
+    pub tick_duration: f32,
+    pub time_scale: f32,
+    pub paused: bool,
+    pub ticks: u64,
+    pub elapsed: f32,
+    accumulator: f32,
+    pending_steps: u32,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        Self::with_tick(1.0 / 60.0)
+    }
+}
+
+impl SimulationClock {
+    pub fn with_tick(tick_duration: f32) -> Self {
+        Self {
+            tick_duration,
+            time_scale: 1.0,
+            paused: false,
+            ticks: 0,
+            elapsed: 0.0,
+            accumulator: 0.0,
+            pending_steps: 0,
+        }
+    }
+
+    // Durée simulée d'un pas, à utiliser à la place de Time::delta_seconds
+    pub fn delta_seconds(&self) -> f32 {
+        self.tick_duration
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.accumulator = 0.0;
+    }
+
+    // Avance d'un seul pas, seulement en pause
+    pub fn request_step(&mut self) {
+        if self.paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    // Nombre de pas à exécuter pour `real_delta` secondes réelles
+    pub fn ticks_for_frame(&mut self, real_delta: f32) -> u32 {
+        if self.paused {
+            return std::mem::take(&mut self.pending_steps);
+        }
+
+        self.accumulator += real_delta * self.time_scale;
+        let mut ticks = 0;
+        while self.accumulator >= self.tick_duration - 1e-6 && ticks < MAX_TICKS_PER_FRAME {
+            self.accumulator -= self.tick_duration;
+            ticks += 1;
+        }
+        if ticks == MAX_TICKS_PER_FRAME {
+            self.accumulator = 0.0;
+        }
+        ticks
+    }
+
+    fn advance(&mut self) {
+        self.ticks += 1;
+        self.elapsed += self.tick_duration;
+    }
+}
+
+pub fn run_simulation_ticks(world: &mut World) {
+    let real_delta = world.resource::<Time>().delta_seconds();
+    let ticks = world.resource_mut::<SimulationClock>().ticks_for_frame(real_delta);
+
+    for _ in 0..ticks {
+        world.run_schedule(SimulationTick);
+        world.resource_mut::<SimulationClock>().advance();
+    }
+}
+
+#[derive(Component)]
+struct ClockLabel;
+
+// Commandes clavier de l'horloge et affichage à l'écran, pour la version avec fenêtre
+pub struct ClockControlPlugin;
+
+impl Plugin for ClockControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_clock_label)
+            .add_systems(Update, (control_clock, update_clock_label).chain());
+    }
+}
+
+fn control_clock(keyboard: Res<Input<KeyCode>>, mut clock: ResMut<SimulationClock>) {
+    if keyboard.just_pressed(KeyCode::Space) {
+        clock.toggle_pause();
+        println!("{}", if clock.paused { "⏸️ Pause" } else { "▶️ Reprise" });
+    }
+
+    if keyboard.just_pressed(KeyCode::N) {
+        clock.request_step();
+    }
+
+    if keyboard.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd]) {
+        let scale = clock.time_scale * 2.0;
+        clock.set_time_scale(scale);
+    }
+
+    if keyboard.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        let scale = clock.time_scale / 2.0;
+        clock.set_time_scale(scale);
+    }
+}
+
+fn spawn_clock_label(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 16.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+        ClockLabel,
+    ));
+}
+
+fn update_clock_label(clock: Res<SimulationClock>, mut label_query: Query<&mut Text, With<ClockLabel>>) {
+    for mut text in label_query.iter_mut() {
+        text.sections[0].value = format!(
+            "{} x{} | pas {} | {:.1}s",
+            if clock.paused { "PAUSE" } else { "LECTURE" },
+            clock.time_scale,
+            clock.ticks,
+            clock.elapsed
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+
+use crate::robots::GameMap;
+
+const SKIN: f32 = 0.01;
+const OVERLAP_TOLERANCE: f32 = 0.001;
+
+pub fn circle_overlaps_obstacles(center: Vec2, radius: f32, game_map: &GameMap) -> bool {
+    let (min_cell, max_cell) = cell_range(center, center, radius, game_map);
+
+    for y in min_cell.y..=max_cell.y {
+        for x in min_cell.x..=max_cell.x {
+            let cell = IVec2::new(x, y);
+            if game_map.is_obstacle_cell(cell) && circle_overlaps_cell(center, radius, cell, game_map) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// Vrai si le cercle empiète sur la case, qu'elle soit un mur ou non
+pub fn circle_overlaps_cell(center: Vec2, radius: f32, cell: IVec2, game_map: &GameMap) -> bool {
+    let half = Vec2::splat(game_map.cell_size / 2.0);
+    let cell_center = game_map.cell_center(cell);
+    let closest = center.clamp(cell_center - half, cell_center + half);
+    closest.distance_squared(center) < (radius - OVERLAP_TOLERANCE).powi(2)
+}
+
+// Déplace un cercle de `from` vers `to` en glissant le long des obstacles rencontrés
+pub fn slide_circle(from: Vec2, to: Vec2, radius: f32, game_map: &GameMap) -> Vec2 {
+    let mut pos = from;
+    let mut motion = to - from;
+
+    for _ in 0..4 {
+        let length = motion.length();
+        if length < 1e-6 {
+            break;
+        }
+
+        match first_hit(pos, motion, radius, game_map) {
+            None => {
+                pos += motion;
+                break;
+            }
+            Some((t, normal)) => {
+                let t = (t - SKIN / length).max(0.0);
+                pos += motion * t;
+                let remaining = motion * (1.0 - t);
+                motion = remaining - normal * remaining.dot(normal);
+            }
+        }
+    }
+
+    pos
+}
+
+// Premier contact (fraction du déplacement, normale) du cercle balayé avec une case obstacle
+pub fn first_hit(pos: Vec2, motion: Vec2, radius: f32, game_map: &GameMap) -> Option<(f32, Vec2)> {
+    let (min_cell, max_cell) = cell_range(pos, pos + motion, radius, game_map);
+    let half = Vec2::splat(game_map.cell_size / 2.0);
+    let mut best: Option<(f32, Vec2)> = None;
+
+    for y in min_cell.y..=max_cell.y {
+        for x in min_cell.x..=max_cell.x {
+            let cell = IVec2::new(x, y);
+            if !game_map.is_obstacle_cell(cell) {
+                continue;
+            }
+
+            let center = game_map.cell_center(cell);
+            if let Some((t, normal)) = sweep_circle_box(pos, motion, radius, center - half, center + half) {
+                if best.is_none_or(|(best_t, _)| t < best_t) {
+                    best = Some((t, normal));
+                }
+            }
+        }
+    }
+
+    best
+}
+
+// Cases couvertes par le déplacement, avec une rangée hors carte pour les bords
+fn cell_range(from: Vec2, to: Vec2, radius: f32, game_map: &GameMap) -> (IVec2, IVec2) {
+    let columns = game_map.obstacles[0].len() as i32;
+    let rows = game_map.obstacles.len() as i32;
+
+    let min = game_map.world_to_cell(from.min(to) - Vec2::splat(radius)).max(IVec2::splat(-1));
+    let max = game_map.world_to_cell(from.max(to) + Vec2::splat(radius)).min(IVec2::new(columns, rows));
+    (min, max)
+}
+
+// Cercle mobile contre une boîte fixe: rayon contre la boîte élargie du rayon (somme de Minkowski)
+fn sweep_circle_box(pos: Vec2, motion: Vec2, radius: f32, min: Vec2, max: Vec2) -> Option<(f32, Vec2)> {
+    let closest = pos.clamp(min, max);
+    let offset = pos - closest;
+
+    if offset.length_squared() < (radius - OVERLAP_TOLERANCE).powi(2) {
+        let normal = offset
+            .try_normalize()
+            .or_else(|| (pos - (min + max) / 2.0).try_normalize())
+            .unwrap_or(Vec2::X);
+        return (motion.dot(normal) < 0.0).then_some((0.0, normal));
+    }
+
+    let corners = [min, max, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y)];
+    let candidates = [
+        ray_box(pos, motion, min - Vec2::new(radius, 0.0), max + Vec2::new(radius, 0.0)),
+        ray_box(pos, motion, min - Vec2::new(0.0, radius), max + Vec2::new(0.0, radius)),
+    ]
+    .into_iter()
+    .chain(corners.into_iter().map(|corner| ray_circle(pos, motion, corner, radius)));
+
+    candidates
+        .flatten()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+}
+
+fn ray_box(pos: Vec2, motion: Vec2, min: Vec2, max: Vec2) -> Option<(f32, Vec2)> {
+    let mut t_enter = f32::NEG_INFINITY;
+    let mut t_exit = f32::INFINITY;
+    let mut normal = Vec2::ZERO;
+
+    for axis in 0..2 {
+        let axis_normal = if axis == 0 { Vec2::X } else { Vec2::Y };
+
+        if motion[axis].abs() < 1e-9 {
+            if pos[axis] < min[axis] || pos[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let t1 = (min[axis] - pos[axis]) / motion[axis];
+        let t2 = (max[axis] - pos[axis]) / motion[axis];
+        let (near, far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+
+        if near > t_enter {
+            t_enter = near;
+            normal = -axis_normal * motion[axis].signum();
+        }
+        t_exit = t_exit.min(far);
+    }
+
+    if t_enter > t_exit || t_exit < 0.0 || t_enter > 1.0 {
+        return None;
+    }
+
+    // Déjà au contact: on ne bloque que le mouvement qui s'enfonce par la face la plus proche
+    if t_enter < 0.0 {
+        let faces = [
+            (pos.x - min.x, -Vec2::X),
+            (max.x - pos.x, Vec2::X),
+            (pos.y - min.y, -Vec2::Y),
+            (max.y - pos.y, Vec2::Y),
+        ];
+        let (_, normal) = faces
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap();
+        return (motion.dot(normal) < 0.0).then_some((0.0, normal));
+    }
+
+    Some((t_enter, normal))
+}
+
+fn ray_circle(pos: Vec2, motion: Vec2, center: Vec2, radius: f32) -> Option<(f32, Vec2)> {
+    let offset = pos - center;
+    let a = motion.length_squared();
+    let b = 2.0 * offset.dot(motion);
+    let c = offset.length_squared() - radius * radius;
+
+    if c <= 0.0 {
+        let normal = offset.try_normalize()?;
+        return (motion.dot(normal) < 0.0).then_some((0.0, normal));
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if a < 1e-12 || discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+
+    Some((t, (pos + motion * t - center) / radius))
+}
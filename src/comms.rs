@@ -0,0 +1,393 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::clock::SimulationClock;
+use crate::robots::{Base, Deposit, GameMap, KnownDeposit, Miner, SimulationRng, Station};
+use crate::sensor::{has_line_of_sight, heading_from_rotation, Sensor};
+use crate::state::{RobotState, StateTransition};
+use crate::stats::SimulationStats;
+
+const RETRY_INTERVAL: f32 = 1.0;
+
+// Global: partage instantané (comportement historique)
+// StationOnly: un robot ne synchronise qu'à portée d'une base (ADR 0004)
+// Mesh: les robots échangent entre eux à portée radio et peuvent relayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CommsModel {
+    #[default]
+    Global,
+    StationOnly,
+    Mesh,
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommsConfig {
+    pub model: CommsModel,
+    pub range: f32,
+    pub line_of_sight: bool,
+    pub relay: bool,
+    pub latency: f32,
+    pub loss_rate: f64,
+    pub message_ttl: f32,
+    pub patch_interval: f32,
+    // Un explorateur rentre à la base si une découverte n'a pas été transmise après ce délai
+    pub report_timeout: f32,
+}
+
+impl Default for CommsConfig {
+    fn default() -> Self {
+        Self {
+            model: CommsModel::Global,
+            range: 150.0,
+            line_of_sight: true,
+            relay: true,
+            latency: 0.5,
+            loss_rate: 0.0,
+            message_ttl: 60.0,
+            patch_interval: 2.0,
+            report_timeout: 20.0,
+        }
+    }
+}
+
+impl CommsConfig {
+    pub fn is_global(&self) -> bool {
+        self.model == CommsModel::Global
+    }
+
+    // Découverte restée sans relais trop longtemps: le robot doit la rapporter lui-même
+    pub fn report_overdue(&self, radio: Option<&Radio>, network: &Network, now: f32) -> bool {
+        !self.is_global()
+            && radio
+                .and_then(|radio| radio.oldest_unreported_discovery(network))
+                .is_some_and(|created_at| now - created_at >= self.report_timeout)
+    }
+
+    // Liaison possible entre deux points; `with_base` si l'un des deux est une base
+    pub fn link(&self, from: Vec2, to: Vec2, with_base: bool, game_map: &GameMap) -> bool {
+        if self.model == CommsModel::StationOnly && !with_base {
+            return false;
+        }
+        from.distance(to) <= self.range && (!self.line_of_sight || has_line_of_sight(from, to, game_map))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Payload {
+    Discovery(KnownDeposit),
+    MapPatch(Vec<IVec2>),
+    TaskClaim { deposit: Vec2, miner: Entity },
+}
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub id: u64,
+    pub origin: Entity,
+    pub created_at: f32,
+    pub payload: Payload,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxEntry {
+    pub message: Message,
+    pub last_sent: Option<f32>,
+}
+
+// Émetteur-récepteur d'un robot et ce qu'il sait localement
+#[derive(Component, Debug, Default)]
+pub struct Radio {
+    pub outbox: Vec<OutboxEntry>,
+    // Messages déjà reçus ou émis, avec leur date de création pour les oublier après `message_ttl`
+    pub seen: HashMap<u64, f32>,
+    pub known_deposits: Vec<KnownDeposit>,
+    pub pending_cells: HashSet<IVec2>,
+    pub patch_timer: f32,
+}
+
+impl Radio {
+    pub fn knows_deposit(&self, entity: Entity) -> bool {
+        self.known_deposits.iter().any(|deposit| deposit.entity == entity)
+    }
+
+    pub fn learn_deposit(&mut self, deposit: &KnownDeposit) {
+        if !self.knows_deposit(deposit.entity) {
+            self.known_deposits.push(deposit.clone());
+        }
+    }
+
+    // Date de création de la plus ancienne découverte que la station n'a pas encore reçue
+    pub fn oldest_unreported_discovery(&self, network: &Network) -> Option<f32> {
+        self.outbox
+            .iter()
+            .filter(|entry| matches!(entry.message.payload, Payload::Discovery(_)))
+            .filter(|entry| !network.station_seen.contains_key(&entry.message.id))
+            .map(|entry| entry.message.created_at)
+            .reduce(f32::min)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recipient {
+    Station,
+    Robot(Entity),
+}
+
+#[derive(Debug, Clone)]
+pub struct InFlight {
+    pub recipient: Recipient,
+    pub message: Message,
+    pub deliver_at: f32,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Network {
+    next_id: u64,
+    pub in_flight: Vec<InFlight>,
+    pub station_seen: HashMap<u64, f32>,
+}
+
+impl Network {
+    pub fn send(&mut self, radio: &mut Radio, origin: Entity, now: f32, payload: Payload) {
+        let message = Message {
+            id: self.next_id,
+            origin,
+            created_at: now,
+            payload,
+        };
+        self.next_id += 1;
+        radio.seen.insert(message.id, message.created_at);
+        radio.outbox.push(OutboxEntry {
+            message,
+            last_sent: None,
+        });
+    }
+
+    // Un message expiré n'est plus relayé: la station et les robots peuvent l'oublier
+    fn forget_expired(&mut self, now: f32, ttl: f32) {
+        self.station_seen.retain(|_, created_at| now - *created_at < ttl);
+        self.in_flight.retain(|flight| now - flight.message.created_at < ttl);
+    }
+
+    fn in_flight_to(&self, recipient: Recipient, id: u64) -> bool {
+        self.in_flight
+            .iter()
+            .any(|flight| flight.recipient == recipient && flight.message.id == id)
+    }
+}
+
+pub fn apply_to_station(payload: &Payload, station: &mut Station) {
+    match payload {
+        Payload::Discovery(deposit) => {
+            station.register_deposit(deposit.entity, deposit.position, deposit.resource, deposit.remaining)
+        }
+        Payload::MapPatch(cells) => station.mapped_cells.extend(cells.iter().copied()),
+        Payload::TaskClaim { deposit, miner } => {
+            station.claims.insert(*miner, *deposit);
+        }
+    }
+}
+
+// Cellules vues par chaque robot, envoyées par paquets à intervalle régulier
+pub fn share_map_patches(
+    config: Res<CommsConfig>,
+    game_map: Res<GameMap>,
+    mut network: ResMut<Network>,
+    mut station: ResMut<Station>,
+    clock: Res<SimulationClock>,
+    stats: Res<SimulationStats>,
+    mut robots_query: Query<(Entity, &Transform, &Sensor, &mut Radio)>,
+) {
+    for (entity, transform, sensor, mut radio) in robots_query.iter_mut() {
+        let origin = transform.translation.truncate();
+        let cells = sensor.visible_cells(origin, heading_from_rotation(transform.rotation), &game_map);
+
+        if config.is_global() {
+            station.mapped_cells.extend(cells);
+            continue;
+        }
+
+        radio.pending_cells.extend(cells);
+        radio.patch_timer += clock.delta_seconds();
+        if radio.patch_timer < config.patch_interval || radio.pending_cells.is_empty() {
+            continue;
+        }
+        radio.patch_timer = 0.0;
+        let mut patch: Vec<IVec2> = radio.pending_cells.drain().collect();
+        patch.sort_by_key(|cell| (cell.y, cell.x));
+        network.send(&mut radio, entity, stats.elapsed, Payload::MapPatch(patch));
+    }
+}
+
+// Un mineur qui part vers un gisement le revendique auprès de la station
+pub fn announce_task_claims(
+    config: Res<CommsConfig>,
+    mut transitions: EventReader<StateTransition>,
+    mut network: ResMut<Network>,
+    mut station: ResMut<Station>,
+    stats: Res<SimulationStats>,
+    mut miners_query: Query<&mut Radio, With<Miner>>,
+) {
+    for transition in transitions.iter() {
+        let RobotState::MovingTo(deposit) = transition.to else {
+            continue;
+        };
+        if matches!(transition.from, RobotState::MovingTo(_) | RobotState::Stuck) {
+            continue;
+        }
+        let Ok(mut radio) = miners_query.get_mut(transition.entity) else {
+            continue;
+        };
+
+        let payload = Payload::TaskClaim {
+            deposit,
+            miner: transition.entity,
+        };
+        if config.is_global() {
+            apply_to_station(&payload, &mut station);
+        } else {
+            network.send(&mut radio, transition.entity, stats.elapsed, payload);
+        }
+    }
+}
+
+pub fn transmit_messages(
+    config: Res<CommsConfig>,
+    game_map: Res<GameMap>,
+    mut network: ResMut<Network>,
+    mut rng: ResMut<SimulationRng>,
+    mut stats: ResMut<SimulationStats>,
+    mut robots_query: Query<(Entity, &Transform, &mut Radio)>,
+    bases_query: Query<&Transform, With<Base>>,
+) {
+    if config.is_global() {
+        return;
+    }
+
+    let now = stats.elapsed;
+    network.forget_expired(now, config.message_ttl);
+    for (_, _, mut radio) in robots_query.iter_mut() {
+        radio.seen.retain(|_, created_at| now - *created_at < config.message_ttl);
+        radio.outbox.retain(|entry| {
+            now - entry.message.created_at < config.message_ttl && !network.station_seen.contains_key(&entry.message.id)
+        });
+    }
+
+    // Premier passage en lecture seule: destinataires de chaque message à (ré)émettre
+    let bases: Vec<Vec2> = bases_query.iter().map(|transform| transform.translation.truncate()).collect();
+    let nodes: Vec<(Entity, Vec2)> = robots_query
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+    let mut sends: Vec<(Entity, usize, Vec<Recipient>)> = Vec::new();
+
+    for (sender, transform, radio) in robots_query.iter() {
+        let position = transform.translation.truncate();
+        let station_link = bases.iter().any(|&base| config.link(position, base, true, &game_map));
+        let neighbours: Vec<(Entity, &Radio)> = nodes
+            .iter()
+            .filter(|(entity, other)| *entity != sender && config.link(position, *other, false, &game_map))
+            .filter_map(|(entity, _)| robots_query.get(*entity).ok())
+            .map(|(entity, _, radio)| (entity, radio))
+            .collect();
+
+        for (index, entry) in radio.outbox.iter().enumerate() {
+            if entry.last_sent.is_some_and(|sent| now - sent < RETRY_INTERVAL) {
+                continue;
+            }
+
+            let id = entry.message.id;
+            let mut recipients = Vec::new();
+            if station_link && !network.station_seen.contains_key(&id) {
+                recipients.push(Recipient::Station);
+            }
+            for (entity, neighbour) in &neighbours {
+                if !neighbour.seen.contains_key(&id) {
+                    recipients.push(Recipient::Robot(*entity));
+                }
+            }
+            recipients.retain(|&recipient| !network.in_flight_to(recipient, id));
+            if !recipients.is_empty() {
+                sends.push((sender, index, recipients));
+            }
+        }
+    }
+
+    for (sender, index, recipients) in sends {
+        let Ok((_, _, mut radio)) = robots_query.get_mut(sender) else {
+            continue;
+        };
+        let entry = &mut radio.outbox[index];
+        entry.last_sent = Some(now);
+        for recipient in recipients {
+            stats.messages_sent += 1;
+            if rng.0.gen_bool(config.loss_rate) {
+                stats.messages_lost += 1;
+                continue;
+            }
+            network.in_flight.push(InFlight {
+                recipient,
+                message: entry.message.clone(),
+                deliver_at: now + config.latency,
+            });
+        }
+    }
+}
+
+pub fn deliver_messages(
+    config: Res<CommsConfig>,
+    mut network: ResMut<Network>,
+    mut station: ResMut<Station>,
+    mut stats: ResMut<SimulationStats>,
+    mut robots_query: Query<&mut Radio>,
+    deposits_query: Query<&Deposit>,
+) {
+    let now = stats.elapsed;
+    let (due, pending): (Vec<InFlight>, Vec<InFlight>) =
+        network.in_flight.drain(..).partition(|flight| flight.deliver_at <= now);
+    network.in_flight = pending;
+
+    for flight in due {
+        let message = flight.message;
+        match flight.recipient {
+            Recipient::Station => {
+                if network.station_seen.insert(message.id, message.created_at).is_some() {
+                    continue;
+                }
+                stats.messages_delivered += 1;
+                // Un gisement épuisé entre-temps n'est plus proposé aux mineurs
+                if let Payload::Discovery(deposit) = &message.payload {
+                    if station.knows_deposit(deposit.entity)
+                        || deposits_query.get(deposit.entity).map_or(true, |found| found.remaining == 0)
+                    {
+                        continue;
+                    }
+                    println!("📡 Découverte {:?} reçue par la station ({:.1}s après)", deposit.position, now - message.created_at);
+                    // Une découverte ne compte qu'une fois, quel que soit le nombre de robots qui l'ont vue
+                    stats.discoveries += 1;
+                }
+                apply_to_station(&message.payload, &mut station);
+            }
+            Recipient::Robot(entity) => {
+                let Ok(mut radio) = robots_query.get_mut(entity) else {
+                    continue;
+                };
+                if radio.seen.insert(message.id, message.created_at).is_some() {
+                    continue;
+                }
+                stats.messages_delivered += 1;
+                if let Payload::Discovery(deposit) = &message.payload {
+                    radio.learn_deposit(deposit);
+                }
+                if config.relay {
+                    radio.outbox.push(OutboxEntry {
+                        message,
+                        last_sent: None,
+                    });
+                }
+            }
+        }
+    }
+}
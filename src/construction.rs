@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::robots::{spawn_base, GameMap, Station};
+use crate::scenario::BaseConfig;
+use crate::stats::SimulationStats;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConstructionConfig {
+    // Minerais dépensés par la station pour chaque avant-poste
+    pub outpost_cost: u32,
+    // Emplacements à construire, dans l'ordre, dès que le stock le permet
+    pub outposts: Vec<BaseConfig>,
+}
+
+impl Default for ConstructionConfig {
+    fn default() -> Self {
+        Self {
+            outpost_cost: 15,
+            outposts: Vec::new(),
+        }
+    }
+}
+
+// File des avant-postes commandés et pas encore construits
+#[derive(Resource, Debug, Default)]
+pub struct OutpostOrders {
+    pub pending: VecDeque<Vec2>,
+}
+
+impl OutpostOrders {
+    pub fn from_config(config: &ConstructionConfig) -> Self {
+        Self {
+            pending: config
+                .outposts
+                .iter()
+                .map(|outpost| Vec2::new(outpost.position.0, outpost.position.1))
+                .collect(),
+        }
+    }
+
+    pub fn order(&mut self, position: Vec2) {
+        self.pending.push_back(position);
+    }
+}
+
+// Retire le coût du stock; None si la station n'a pas assez de minerais
+pub fn pay_outpost(station: &mut Station, cost: u32) -> Option<u32> {
+    if station.minerals < cost {
+        return None;
+    }
+    station.minerals -= cost;
+    Some(station.minerals)
+}
+
+pub fn build_outposts(
+    mut commands: Commands,
+    config: Res<ConstructionConfig>,
+    mut orders: ResMut<OutpostOrders>,
+    mut station: ResMut<Station>,
+    mut stats: ResMut<SimulationStats>,
+    mut game_map: ResMut<GameMap>,
+) {
+    let Some(&position) = orders.pending.front() else {
+        return;
+    };
+    let Some(remaining) = pay_outpost(&mut station, config.outpost_cost) else {
+        return;
+    };
+
+    orders.pending.pop_front();
+    spawn_base(&mut commands, &mut game_map, &position);
+    stats.outposts_built += 1;
+    println!(
+        "🏗️ Avant-poste construit en {:?} pour {} minerais ({} restants)",
+        position, config.outpost_cost, remaining
+    );
+}
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::SimulationClock;
+use crate::robots::{nearest_base, Base, GameMap};
+use crate::stats::SimulationStats;
+
+// Distance à une base en dessous de laquelle un robot se recharge au panneau de la base
+pub const CHARGING_DISTANCE: f32 = 40.0;
+// Marge sur l'énergie estimée pour rentrer, les chemins réels contournent les obstacles
+const RETURN_MARGIN: f32 = 1.5;
+// Durée de l'aube et du crépuscule, en fraction de la période
+const TWILIGHT: f32 = 0.05;
+const NIGHT_TINT_ALPHA: f32 = 0.45;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaylightConfig {
+    // Durée d'un cycle jour/nuit complet; 0 garde un jour permanent
+    pub day_length: f32,
+    // Part du cycle éclairée, la simulation commence au lever du jour
+    pub daylight_ratio: f32,
+    // Facteur de portée des capteurs en pleine nuit
+    pub night_sensor_factor: f32,
+    // Charge consommée par pixel parcouru, la batterie pleine vaut 1; 0 désactive la consommation
+    pub drain_per_distance: f32,
+    // Charge par seconde des panneaux embarqués, en plein jour
+    pub panel_charge_rate: f32,
+    // Charge par seconde près d'une base, en plein jour
+    pub base_charge_rate: f32,
+    // Charge gardée en plus de celle du retour avant de rentrer se recharger
+    pub reserve: f32,
+}
+
+impl Default for DaylightConfig {
+    fn default() -> Self {
+        Self {
+            day_length: 0.0,
+            daylight_ratio: 0.5,
+            night_sensor_factor: 0.6,
+            drain_per_distance: 0.0,
+            panel_charge_rate: 0.005,
+            base_charge_rate: 0.05,
+            reserve: 0.15,
+        }
+    }
+}
+
+impl DaylightConfig {
+    // Luminosité de 0 (nuit) à 1 (jour), avec une transition au crépuscule et à l'aube
+    pub fn light_level(&self, elapsed: f32) -> f32 {
+        if self.day_length <= 0.0 || self.daylight_ratio >= 1.0 {
+            return 1.0;
+        }
+        let phase = (elapsed / self.day_length).fract();
+        if phase < self.daylight_ratio {
+            return 1.0;
+        }
+        let twilight = TWILIGHT.min((1.0 - self.daylight_ratio) / 2.0);
+        let into_night = (phase - self.daylight_ratio).min(1.0 - phase);
+        (1.0 - into_night / twilight).max(0.0)
+    }
+
+    pub fn is_daylight(&self, elapsed: f32) -> bool {
+        self.light_level(elapsed) >= 0.5
+    }
+
+    pub fn sensor_factor(&self, elapsed: f32) -> f32 {
+        self.night_sensor_factor + (1.0 - self.night_sensor_factor) * self.light_level(elapsed)
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Battery {
+    pub charge: f32,
+    last_position: Option<Vec2>,
+}
+
+impl Default for Battery {
+    fn default() -> Self {
+        Self {
+            charge: 1.0,
+            last_position: None,
+        }
+    }
+}
+
+impl Battery {
+    pub fn is_empty(&self) -> bool {
+        self.charge <= 0.0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.charge >= 1.0
+    }
+
+    // Vrai quand il ne reste que de quoi rentrer à une base située à `distance`, plus la réserve
+    pub fn needs_recharge(&self, distance: f32, config: &DaylightConfig) -> bool {
+        config.drain_per_distance > 0.0
+            && self.charge <= config.reserve + distance * config.drain_per_distance * RETURN_MARGIN
+    }
+
+    // Consomme la charge du trajet depuis la dernière position connue
+    pub fn record_travel(&mut self, position: Vec2, drain_per_distance: f32) {
+        if let Some(last) = self.last_position {
+            self.charge = (self.charge - last.distance(position) * drain_per_distance).max(0.0);
+        }
+        self.last_position = Some(position);
+    }
+
+    pub fn recharge(&mut self, amount: f32) {
+        self.charge = (self.charge + amount).min(1.0);
+    }
+}
+
+#[derive(Component)]
+pub struct NightOverlay;
+
+// Consommation avec la distance parcourue; les panneaux, embarqués ou de la base, ne chargent que de jour
+pub fn update_batteries(
+    config: Res<DaylightConfig>,
+    clock: Res<SimulationClock>,
+    mut stats: ResMut<SimulationStats>,
+    mut robots_query: Query<(Entity, &Transform, &mut Battery)>,
+    bases_query: Query<&Transform, With<Base>>,
+) {
+    let bases: Vec<Vec2> = bases_query.iter().map(|transform| transform.translation.truncate()).collect();
+    let light = config.light_level(stats.elapsed);
+
+    for (entity, transform, mut battery) in robots_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let was_empty = battery.is_empty();
+        battery.record_travel(position, config.drain_per_distance);
+
+        let at_base = position.distance(nearest_base(position, &bases)) < CHARGING_DISTANCE;
+        let rate = if at_base { config.base_charge_rate } else { config.panel_charge_rate };
+        battery.recharge(rate * light * clock.delta_seconds());
+
+        if battery.is_empty() && !was_empty {
+            stats.batteries_depleted += 1;
+            println!("🪫 Robot {:?} à court d'énergie en {:?}", entity, position);
+        }
+    }
+}
+
+pub fn spawn_night_overlay(mut commands: Commands, game_map: Res<GameMap>) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.0, 0.02, 0.1, 0.0),
+                custom_size: Some(game_map.size),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 0.9),
+            ..default()
+        },
+        NightOverlay,
+    ));
+}
+
+pub fn update_night_overlay(
+    config: Res<DaylightConfig>,
+    stats: Res<SimulationStats>,
+    mut overlay_query: Query<&mut Sprite, With<NightOverlay>>,
+) {
+    let alpha = (1.0 - config.light_level(stats.elapsed)) * NIGHT_TINT_ALPHA;
+    for mut sprite in overlay_query.iter_mut() {
+        if sprite.color.a() != alpha {
+            sprite.color.set_a(alpha);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::camera::{cursor_world_position, MainCamera};
+use crate::collision::circle_overlaps_obstacles;
+use crate::construction::OutpostOrders;
+use crate::motion::Kinematics;
+use crate::objectives::ScientificSite;
+use crate::pathfinding::PlannedPath;
+use crate::robots::{
+    clear_obstacles_around_position, spawn_persistent_resource, spawn_scientific_site, Base, Deposit, Explorer,
+    GameMap, Resource, RobotBody,
+};
+use crate::scenario::{
+    grid_rows, BaseConfig, MapGenerator, ResourcePlacement, RobotKind, RobotSpec, Scenario, SensorConfig,
+};
+use crate::sensor::Sensor;
+use crate::spatial::{SpatialIndex, SpatialKind};
+use crate::watchdog::StuckWatchdog;
+
+pub const EXPORT_PATH: &str = "edited_scenario.ron";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Brush {
+    #[default]
+    Energy,
+    Mineral,
+    ScientificSite,
+    Outpost,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct MapEditor {
+    pub active: bool,
+    pub brush: Brush,
+    pub dragging: Option<Entity>,
+}
+
+// Inverse une case de la carte; refusé si un robot ou une base s'y trouverait emmuré
+pub fn toggle_obstacle(game_map: &mut GameMap, position: Vec2, occupied: &[(Vec2, f32)]) -> Option<bool> {
+    let cell = game_map.world_to_cell(position);
+    if !game_map.in_bounds(cell) {
+        return None;
+    }
+
+    let wall = !game_map.obstacles[cell.y as usize][cell.x as usize];
+    game_map.obstacles[cell.y as usize][cell.x as usize] = wall;
+    if wall && occupied.iter().any(|&(center, radius)| circle_overlaps_obstacles(center, radius, game_map)) {
+        game_map.obstacles[cell.y as usize][cell.x as usize] = false;
+        return None;
+    }
+    Some(wall)
+}
+
+// Scénario reproduisant l'état courant: grille éditée, gisements restants et robots à leur place
+pub fn edited_scenario(
+    base: &Scenario,
+    game_map: &GameMap,
+    deposits: &[(Resource, Vec2, u32)],
+    sites: &[Vec2],
+    robots: Vec<RobotSpec>,
+) -> Scenario {
+    let points = |resource: Resource| -> Vec<(f32, f32)> {
+        deposits
+            .iter()
+            .filter(|(kind, ..)| *kind == resource)
+            .map(|(_, pos, _)| (pos.x, pos.y))
+            .collect()
+    };
+    // Les gisements entamés gardent leur quantité restante pour reproduire l'état exporté
+    let remaining = deposits
+        .iter()
+        .filter(|(resource, _, amount)| *amount < resource.initial_quantity())
+        .map(|(_, pos, amount)| ((pos.x, pos.y), *amount))
+        .collect();
+
+    let mut scenario = base.clone();
+    scenario.name = format!("{} (édité)", base.name);
+    scenario.map.generator = MapGenerator::Grid(grid_rows(game_map));
+    scenario.resources = ResourcePlacement::Fixed {
+        energy: points(Resource::Energy),
+        minerals: points(Resource::Mineral),
+        scientific_sites: sites.iter().map(|pos| (pos.x, pos.y)).collect(),
+        remaining,
+    };
+    scenario.robots = robots;
+    scenario
+}
+
+pub struct MapEditorPlugin;
+
+impl Plugin for MapEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapEditor>()
+            .add_systems(Update, (control_editor, edit_map_with_mouse, export_edited_map).chain());
+    }
+}
+
+fn control_editor(keyboard: Res<Input<KeyCode>>, mut editor: ResMut<MapEditor>) {
+    if keyboard.just_pressed(KeyCode::E) {
+        editor.active = !editor.active;
+        editor.dragging = None;
+        println!("✏️ Mode édition {}", if editor.active { "activé" } else { "désactivé" });
+    }
+
+    if !editor.active {
+        return;
+    }
+    for (key, brush) in [
+        (KeyCode::Key1, Brush::Energy),
+        (KeyCode::Key2, Brush::Mineral),
+        (KeyCode::Key3, Brush::ScientificSite),
+        (KeyCode::Key4, Brush::Outpost),
+    ] {
+        if keyboard.just_pressed(key) {
+            editor.brush = brush;
+            println!("🖌️ Pinceau: {:?}", brush);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn edit_map_with_mouse(
+    mut commands: Commands,
+    mouse: Res<Input<MouseButton>>,
+    keyboard: Res<Input<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    spatial_index: Res<SpatialIndex>,
+    mut editor: ResMut<MapEditor>,
+    mut orders: ResMut<OutpostOrders>,
+    mut game_map: ResMut<GameMap>,
+    mut robots_query: Query<(&mut Transform, &RobotBody, &mut Kinematics, &mut PlannedPath, &mut StuckWatchdog)>,
+    bases_query: Query<&Transform, (With<Base>, Without<RobotBody>)>,
+) {
+    if !editor.active {
+        return;
+    }
+    if mouse.just_released(MouseButton::Left) {
+        editor.dragging = None;
+    }
+    let Some(cursor) = cursor_world_position(&windows, &camera_query) else {
+        return;
+    };
+
+    if let Some(entity) = editor.dragging {
+        if let Ok((mut transform, body, mut kinematics, mut path, mut watchdog)) = robots_query.get_mut(entity) {
+            if !circle_overlaps_obstacles(cursor, body.radius, &game_map) {
+                transform.translation.x = cursor.x;
+                transform.translation.y = cursor.y;
+                kinematics.speed = 0.0;
+                path.clear();
+                watchdog.reset(cursor);
+            }
+        }
+        return;
+    }
+
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        // Un avant-poste n'est pas posé gratuitement: il est commandé à la station
+        if editor.brush == Brush::Outpost {
+            orders.order(cursor);
+            println!("📐 Avant-poste commandé en {:?} ({} en attente)", cursor, orders.pending.len());
+            return;
+        }
+        clear_obstacles_around_position(&mut game_map, &cursor, 1);
+        match editor.brush {
+            Brush::Energy => {
+                spawn_persistent_resource(&mut commands, &cursor, Color::YELLOW, Resource::Energy);
+            }
+            Brush::Mineral => {
+                spawn_persistent_resource(&mut commands, &cursor, Color::BLUE, Resource::Mineral);
+            }
+            Brush::ScientificSite => spawn_scientific_site(&mut commands, &cursor),
+            Brush::Outpost => {}
+        }
+        println!("📍 {:?} placé en {:?}", editor.brush, cursor);
+        return;
+    }
+
+    if let Some((entity, _, _)) = spatial_index.nearest(cursor, 20.0, SpatialKind::Robot) {
+        editor.dragging = Some(entity);
+        return;
+    }
+
+    let occupied: Vec<(Vec2, f32)> = robots_query
+        .iter()
+        .map(|(transform, body, ..)| (transform.translation.truncate(), body.radius))
+        .chain(bases_query.iter().map(|transform| (transform.translation.truncate(), 15.0)))
+        .collect();
+    match toggle_obstacle(&mut game_map, cursor, &occupied) {
+        Some(true) => println!("🧱 Mur ajouté en {:?}", game_map.world_to_cell(cursor)),
+        Some(false) => println!("🧹 Mur retiré en {:?}", game_map.world_to_cell(cursor)),
+        None => {}
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn export_edited_map(
+    keyboard: Res<Input<KeyCode>>,
+    editor: Res<MapEditor>,
+    scenario: Res<Scenario>,
+    game_map: Res<GameMap>,
+    deposits_query: Query<(&Transform, &Resource, &Deposit)>,
+    sites_query: Query<&Transform, With<ScientificSite>>,
+    robots_query: Query<(&Transform, &RobotBody, &Kinematics, Option<&Sensor>, Option<&Explorer>)>,
+    bases_query: Query<&Transform, With<Base>>,
+    orders: Res<OutpostOrders>,
+) {
+    if !editor.active || !keyboard.just_pressed(KeyCode::X) {
+        return;
+    }
+
+    let deposits: Vec<(Resource, Vec2, u32)> = deposits_query
+        .iter()
+        .filter(|(_, _, deposit)| deposit.remaining > 0)
+        .map(|(transform, resource, deposit)| (*resource, transform.translation.truncate(), deposit.remaining))
+        .collect();
+    let sites: Vec<Vec2> = sites_query.iter().map(|transform| transform.translation.truncate()).collect();
+    let robots = robots_query
+        .iter()
+        .map(|(transform, body, kinematics, sensor, explorer)| RobotSpec {
+            kind: if explorer.is_some() { RobotKind::Explorer } else { RobotKind::Miner },
+            position: (transform.translation.x, transform.translation.y),
+            count: 1,
+            spacing: (0.0, 0.0),
+            radius: Some(body.radius),
+            motion: Some(kinematics.config),
+            sensor: sensor.map(|sensor| SensorConfig {
+                range: sensor.range,
+                field_of_view_degrees: sensor.field_of_view.to_degrees(),
+                false_negative_rate: sensor.false_negative_rate,
+                position_noise: sensor.position_noise,
+            }),
+        })
+        .collect();
+
+    let mut edited = edited_scenario(&scenario, &game_map, &deposits, &sites, robots);
+    // Les avant-postes déjà construits deviennent des bases, les commandes restantes sont gardées
+    edited.bases = bases_query
+        .iter()
+        .map(|transform| BaseConfig {
+            position: (transform.translation.x, transform.translation.y),
+        })
+        .collect();
+    edited.construction.outposts = orders
+        .pending
+        .iter()
+        .map(|position| BaseConfig {
+            position: (position.x, position.y),
+        })
+        .collect();
+    match edited.save(EXPORT_PATH) {
+        Ok(()) => println!("💾 Carte exportée dans {}", EXPORT_PATH),
+        Err(error) => eprintln!("❌ Export impossible: {}", error),
+    }
+}
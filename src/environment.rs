@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::{circle_overlaps_cell, circle_overlaps_obstacles};
+use crate::daylight::DaylightConfig;
+use crate::robots::{
+    spawn_persistent_resource, Base, ExploredZones, GameMap, Resource, RobotBody, SimulationRng, Station,
+};
+use crate::sensor::Sensor;
+use crate::spatial::{SpatialIndex, SpatialKind};
+use crate::stats::SimulationStats;
+
+const PLACEMENT_ATTEMPTS: usize = 20;
+// Distance minimale entre un éboulement ou un nouveau gisement et une base, un robot ou une ressource
+const EVENT_CLEARANCE: f32 = 60.0;
+// Espace qu'un éboulement laisse libre, case par case, autour d'une base, d'un robot ou d'une ressource
+const ROCKFALL_MARGIN: f32 = EVENT_CLEARANCE / 2.0;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentConfig {
+    // Délai moyen entre deux événements; 0 garde une carte statique
+    pub event_interval: f32,
+    pub storm_weight: u32,
+    pub rockfall_weight: u32,
+    pub deposit_weight: u32,
+    pub storm_radius: f32,
+    pub storm_duration: f32,
+    // Facteur de portée des capteurs dans la tempête
+    pub storm_sensor_factor: f32,
+    // Rayon d'un éboulement, en cases
+    pub rockfall_radius: i32,
+}
+
+impl Default for EnvironmentConfig {
+    fn default() -> Self {
+        Self {
+            event_interval: 0.0,
+            storm_weight: 1,
+            rockfall_weight: 1,
+            deposit_weight: 1,
+            storm_radius: 200.0,
+            storm_duration: 40.0,
+            storm_sensor_factor: 0.4,
+            rockfall_radius: 1,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum EnvironmentEvent {
+    DustStorm { center: Vec2, radius: f32, duration: f32 },
+    Rockfall { cells: Vec<IVec2> },
+    NewDeposit { position: Vec2, resource: Resource },
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DustStorm {
+    pub radius: f32,
+    pub ends_at: f32,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct EnvironmentClock {
+    pub next_event_at: Option<f32>,
+}
+
+// Cases d'un éboulement centré sur `center`, limitées à la carte
+pub fn rockfall_cells(game_map: &GameMap, center: IVec2, radius: i32) -> Vec<IVec2> {
+    let mut cells = Vec::new();
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            let cell = center + IVec2::new(dx, dy);
+            if dx * dx + dy * dy <= radius * radius && game_map.in_bounds(cell) {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
+
+// Vrai si aucune case de l'éboulement ne touche un des cercles (position, rayon) à protéger
+pub fn rockfall_is_clear(game_map: &GameMap, cells: &[IVec2], protected: &[(Vec2, f32)]) -> bool {
+    protected.iter().all(|&(position, radius)| {
+        !cells
+            .iter()
+            .any(|&cell| circle_overlaps_cell(position, radius, cell, game_map))
+    })
+}
+
+// Portée réduite dans les tempêtes; la plus forte atténuation l'emporte
+pub fn storm_attenuation(position: Vec2, storms: &[(Vec2, f32)], factor: f32) -> f32 {
+    if storms.iter().any(|&(center, radius)| position.distance(center) <= radius) {
+        factor
+    } else {
+        1.0
+    }
+}
+
+fn random_point(game_map: &GameMap, rng: &mut impl Rng) -> Vec2 {
+    let margin = game_map.cell_size * 3.0;
+    Vec2::new(
+        rng.gen_range(-game_map.size.x / 2.0 + margin..game_map.size.x / 2.0 - margin),
+        rng.gen_range(-game_map.size.y / 2.0 + margin..game_map.size.y / 2.0 - margin),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn trigger_environment_events(
+    mut commands: Commands,
+    config: Res<EnvironmentConfig>,
+    mut environment_clock: ResMut<EnvironmentClock>,
+    stats: Res<SimulationStats>,
+    mut rng: ResMut<SimulationRng>,
+    mut game_map: ResMut<GameMap>,
+    spatial_index: Res<SpatialIndex>,
+    bases_query: Query<&Transform, With<Base>>,
+    robots_query: Query<(&Transform, &RobotBody)>,
+    mut events: EventWriter<EnvironmentEvent>,
+) {
+    if config.event_interval <= 0.0 {
+        return;
+    }
+    let next_event_at = *environment_clock
+        .next_event_at
+        .get_or_insert_with(|| config.event_interval * rng.0.gen_range(0.5..1.5));
+    if stats.elapsed < next_event_at {
+        return;
+    }
+    environment_clock.next_event_at = Some(stats.elapsed + config.event_interval * rng.0.gen_range(0.5..1.5));
+
+    let weights = [config.storm_weight, config.rockfall_weight, config.deposit_weight];
+    let total: u32 = weights.iter().sum();
+    if total == 0 {
+        return;
+    }
+    let mut roll = rng.0.gen_range(0..total);
+    let kind = weights
+        .iter()
+        .position(|&weight| {
+            if roll < weight {
+                return true;
+            }
+            roll -= weight;
+            false
+        })
+        .unwrap_or(0);
+
+    let bases: Vec<Vec2> = bases_query.iter().map(|transform| transform.translation.truncate()).collect();
+    let clear_of_everything = |point: Vec2, game_map: &GameMap| {
+        bases.iter().all(|base| base.distance(point) > EVENT_CLEARANCE)
+            && spatial_index.query_radius(point, EVENT_CLEARANCE, SpatialKind::Robot).is_empty()
+            && spatial_index.query_radius(point, EVENT_CLEARANCE, SpatialKind::Resource).is_empty()
+            && !game_map.is_obstacle_cell(game_map.world_to_cell(point))
+    };
+
+    match kind {
+        0 => {
+            let center = random_point(&game_map, &mut rng.0);
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(0.8, 0.6, 0.3, 0.25),
+                        custom_size: Some(Vec2::splat(config.storm_radius * 2.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(center.x, center.y, 0.8),
+                    ..default()
+                },
+                DustStorm {
+                    radius: config.storm_radius,
+                    ends_at: stats.elapsed + config.storm_duration,
+                },
+            ));
+            println!("🌪️ Tempête de poussière en {:?} pendant {:.0}s", center, config.storm_duration);
+            events.send(EnvironmentEvent::DustStorm {
+                center,
+                radius: config.storm_radius,
+                duration: config.storm_duration,
+            });
+        }
+        1 => {
+            // Le centre seul ne suffit pas pour un grand rayon: chaque case doit rester à l'écart
+            let protected: Vec<(Vec2, f32)> = bases
+                .iter()
+                .map(|&base| (base, ROCKFALL_MARGIN))
+                .chain(
+                    robots_query
+                        .iter()
+                        .map(|(transform, body)| (transform.translation.truncate(), body.radius + ROCKFALL_MARGIN)),
+                )
+                .chain(
+                    spatial_index
+                        .query_radius(Vec2::ZERO, game_map.size.length(), SpatialKind::Resource)
+                        .into_iter()
+                        .map(|(_, position)| (position, ROCKFALL_MARGIN)),
+                )
+                .collect();
+            let Some((center, cells)) = (0..PLACEMENT_ATTEMPTS)
+                .map(|_| random_point(&game_map, &mut rng.0))
+                .filter(|&point| clear_of_everything(point, &game_map))
+                .map(|point| (point, rockfall_cells(&game_map, game_map.world_to_cell(point), config.rockfall_radius)))
+                .find(|(_, cells)| rockfall_is_clear(&game_map, cells, &protected))
+            else {
+                return;
+            };
+            for cell in &cells {
+                game_map.obstacles[cell.y as usize][cell.x as usize] = true;
+            }
+            println!("🪨 Éboulement en {:?} ({} cases)", center, cells.len());
+            events.send(EnvironmentEvent::Rockfall { cells });
+        }
+        _ => {
+            let Some(position) = (0..PLACEMENT_ATTEMPTS)
+                .map(|_| random_point(&game_map, &mut rng.0))
+                .find(|&point| clear_of_everything(point, &game_map) && !circle_overlaps_obstacles(point, 10.0, &game_map))
+            else {
+                return;
+            };
+            let (resource, color) = if rng.0.gen_bool(0.5) {
+                (Resource::Energy, Color::YELLOW)
+            } else {
+                (Resource::Mineral, Color::BLUE)
+            };
+            spawn_persistent_resource(&mut commands, &position, color, resource);
+            println!("✨ Nouveau gisement {:?} apparu en {:?}", resource, position);
+            events.send(EnvironmentEvent::NewDeposit { position, resource });
+        }
+    }
+}
+
+// Un éboulement rend obsolète la carte connue des cases touchées; les chemins calculés sont
+// effacés par `invalidate_paths` dès que la GameMap change
+pub fn react_to_environment(
+    mut events: EventReader<EnvironmentEvent>,
+    mut explored_zones: ResMut<ExploredZones>,
+    mut station: ResMut<Station>,
+) {
+    for event in events.iter() {
+        let EnvironmentEvent::Rockfall { cells } = event else {
+            continue;
+        };
+        for cell in cells {
+            explored_zones.grid[cell.y as usize][cell.x as usize] = false;
+            station.mapped_cells.remove(cell);
+        }
+    }
+}
+
+// La portée des capteurs combine les tempêtes et la baisse de luminosité la nuit
+pub fn update_storms(
+    mut commands: Commands,
+    config: Res<EnvironmentConfig>,
+    daylight: Res<DaylightConfig>,
+    stats: Res<SimulationStats>,
+    storms_query: Query<(Entity, &Transform, &DustStorm)>,
+    mut sensors_query: Query<(&Transform, &mut Sensor)>,
+) {
+    let mut storms = Vec::new();
+    for (entity, transform, storm) in storms_query.iter() {
+        if stats.elapsed >= storm.ends_at {
+            println!("🌤️ Fin de la tempête en {:?}", transform.translation.truncate());
+            commands.entity(entity).despawn();
+        } else {
+            storms.push((transform.translation.truncate(), storm.radius));
+        }
+    }
+
+    let night_factor = daylight.sensor_factor(stats.elapsed);
+    for (transform, mut sensor) in sensors_query.iter_mut() {
+        let attenuation =
+            storm_attenuation(transform.translation.truncate(), &storms, config.storm_sensor_factor) * night_factor;
+        if sensor.attenuation != attenuation {
+            sensor.attenuation = attenuation;
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+use crate::clock::SimulationClock;
+use crate::objectives::MissionStatus;
+use crate::robots::SimulationPlugin;
+use crate::scenario::Scenario;
+use crate::stats::SimulationStats;
+
+pub const HEADLESS_TIME_STEP: f32 = 1.0 / 30.0;
+
+// Simulation sans fenêtre avec un pas de temps fixe, donc reproductible pour une graine donnée
+pub fn headless_app(scenario: Scenario) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(HEADLESS_TIME_STEP)))
+        .insert_resource(SimulationClock::with_tick(HEADLESS_TIME_STEP))
+        .add_plugins(SimulationPlugin::new(scenario));
+    app
+}
+
+// Fait tourner la simulation jusqu'à la fin de la mission ou jusqu'à `max_time` secondes simulées
+pub fn run_mission(app: &mut App, max_time: f32) -> MissionStatus {
+    loop {
+        app.update();
+        let status = app.world.resource::<MissionStatus>();
+        if !status.is_running() || app.world.resource::<SimulationStats>().elapsed >= max_time {
+            return status.clone();
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+use crate::camera::SelectedRobot;
+use crate::daylight::Battery;
+use crate::pathfinding::PlannedPath;
+use crate::robots::{Cargo, Explorer, Station};
+use crate::state::{RobotState, StateTransition};
+use crate::stats::SimulationStats;
+use crate::watchdog::RobotStuck;
+
+pub const EVENT_HISTORY: usize = 8;
+const LISTED_DEPOSITS: usize = 10;
+
+#[derive(Component)]
+struct RobotPanel;
+
+#[derive(Component)]
+struct StationPanel;
+
+// Derniers événements de chaque robot, pour le panneau d'inspection
+#[derive(Resource, Debug, Default)]
+pub struct RobotEventLog {
+    entries: HashMap<Entity, VecDeque<String>>,
+}
+
+impl RobotEventLog {
+    pub fn record(&mut self, entity: Entity, entry: String) {
+        let entries = self.entries.entry(entity).or_default();
+        if entries.len() == EVENT_HISTORY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    pub fn recent(&self, entity: Entity) -> Vec<String> {
+        self.entries
+            .get(&entity)
+            .map(|entries| entries.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+pub struct RobotDetails {
+    pub entity: Entity,
+    pub kind: &'static str,
+    pub state: RobotState,
+    pub position: Vec2,
+    pub target: Option<Vec2>,
+    pub path_length: Option<(usize, f32)>,
+    pub cargo: Option<String>,
+    pub battery: Option<f32>,
+    pub events: Vec<String>,
+}
+
+pub fn robot_panel_text(details: &RobotDetails) -> String {
+    let mut lines = vec![
+        format!("{} {:?}", details.kind, details.entity),
+        format!("État : {}", details.state.name()),
+        format!("Position : ({:.0}, {:.0})", details.position.x, details.position.y),
+        match details.target {
+            Some(target) => format!("Cible : ({:.0}, {:.0})", target.x, target.y),
+            None => "Cible : aucune".into(),
+        },
+        match details.path_length {
+            Some((waypoints, distance)) => format!("Chemin : {} étapes, {:.0} px", waypoints, distance),
+            None => "Chemin : direct".into(),
+        },
+        match details.battery {
+            Some(charge) => format!("Batterie : {:.0}%", charge * 100.0),
+            None => "Batterie : -".into(),
+        },
+    ];
+    if let Some(cargo) = &details.cargo {
+        lines.push(format!("Cargaison : {}", cargo));
+    }
+    lines.push("Événements :".into());
+    lines.extend(details.events.iter().rev().map(|event| format!("  {}", event)));
+    lines.join("\n")
+}
+
+pub fn station_panel_text(station: &Station) -> String {
+    let mut lines = vec![
+        "Station".to_string(),
+        format!("Énergie : {}  Minerais : {}", station.energy, station.minerals),
+        format!("Gisements connus : {}", station.known_deposits.len()),
+    ];
+    for deposit in station.known_deposits.iter().take(LISTED_DEPOSITS) {
+        lines.push(format!(
+            "  {:?} ({:.0}, {:.0}) reste {}",
+            deposit.resource, deposit.position.x, deposit.position.y, deposit.remaining
+        ));
+    }
+    if station.known_deposits.len() > LISTED_DEPOSITS {
+        lines.push(format!("  ... et {} autres", station.known_deposits.len() - LISTED_DEPOSITS));
+    }
+    lines.join("\n")
+}
+
+pub fn record_robot_events(
+    mut transitions: EventReader<StateTransition>,
+    mut stuck_events: EventReader<RobotStuck>,
+    stats: Res<SimulationStats>,
+    mut log: ResMut<RobotEventLog>,
+) {
+    for transition in transitions.iter() {
+        log.record(
+            transition.entity,
+            format!("[{:.1}s] {} -> {}", stats.elapsed, transition.from.name(), transition.to.name()),
+        );
+    }
+    for stuck in stuck_events.iter() {
+        log.record(stuck.entity, format!("[{:.1}s] bloqué (tentative {})", stats.elapsed, stuck.attempts));
+    }
+}
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RobotEventLog>()
+            .add_systems(Startup, spawn_panels)
+            .add_systems(Update, (record_robot_events, update_robot_panel, update_station_panel).chain());
+    }
+}
+
+fn spawn_panels(mut commands: Commands) {
+    let panel = |top: Option<f32>, bottom: Option<f32>| {
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 14.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            top: top.map_or(Val::Auto, Val::Px),
+            bottom: bottom.map_or(Val::Auto, Val::Px),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        })
+        .with_background_color(Color::rgba(0.0, 0.0, 0.0, 0.6))
+    };
+
+    commands.spawn((panel(Some(40.0), None), RobotPanel));
+    commands.spawn((panel(None, Some(10.0)), StationPanel));
+}
+
+#[allow(clippy::type_complexity)]
+fn update_robot_panel(
+    selected: Res<SelectedRobot>,
+    log: Res<RobotEventLog>,
+    robots_query: Query<(
+        &Transform,
+        &RobotState,
+        Option<&PlannedPath>,
+        Option<&Cargo>,
+        Option<&Battery>,
+        Option<&Explorer>,
+    )>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), With<RobotPanel>>,
+) {
+    let details = selected.0.and_then(|entity| {
+        let (transform, state, path, cargo, battery, explorer) = robots_query.get(entity).ok()?;
+        let position = transform.translation.truncate();
+        let target = match state {
+            RobotState::MovingTo(target) => Some(*target),
+            _ => path.and_then(|path| path.goal),
+        };
+        let path_length = path.filter(|path| !path.waypoints.is_empty()).map(|path| {
+            let distance = path
+                .waypoints
+                .iter()
+                .fold((position, 0.0), |(previous, total), &waypoint| (waypoint, total + previous.distance(waypoint)))
+                .1;
+            (path.waypoints.len(), distance)
+        });
+
+        Some(RobotDetails {
+            entity,
+            kind: if explorer.is_some() { "Explorateur" } else { "Mineur" },
+            state: *state,
+            position,
+            target,
+            path_length,
+            cargo: cargo.map(|cargo| match cargo.resource {
+                Some(resource) => format!("{} {:?}", cargo.amount, resource),
+                None => "vide".into(),
+            }),
+            battery: battery.map(|battery| battery.charge),
+            events: log.recent(entity),
+        })
+    });
+
+    for (mut text, mut visibility) in panel_query.iter_mut() {
+        match &details {
+            Some(details) => {
+                text.sections[0].value = robot_panel_text(details);
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn update_station_panel(station: Res<Station>, mut panel_query: Query<&mut Text, With<StationPanel>>) {
+    if !station.is_changed() {
+        return;
+    }
+    for mut text in panel_query.iter_mut() {
+        text.sections[0].value = station_panel_text(&station);
+    }
+}
@@ -1,2 +1,26 @@
+pub mod batch;
+pub mod behavior;
+pub mod camera;
+pub mod clock;
+pub mod collision;
+pub mod comms;
+pub mod construction;
+pub mod daylight;
+pub mod editor;
+pub mod environment;
+pub mod headless;
+pub mod inspector;
+pub mod manual;
+pub mod motion;
+pub mod objectives;
+pub mod pathfinding;
+pub mod production;
+pub mod reliability;
 pub mod robots;
-
+pub mod scenario;
+pub mod scripting;
+pub mod sensor;
+pub mod spatial;
+pub mod state;
+pub mod stats;
+pub mod watchdog;
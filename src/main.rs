@@ -1,8 +1,34 @@
 use bevy::prelude::*;
 
-mod robots;
+use simulation_robots::headless::{headless_app, run_mission};
+use simulation_robots::scenario::Scenario;
+use simulation_robots::{camera, clock, editor, inspector, manual, robots};
 
 fn main() {
+    // Usage: simulation_robots [--headless] [scenario.ron]
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let headless = args.iter().any(|arg| arg == "--headless");
+    let scenario = match args.iter().find(|arg| !arg.starts_with("--")) {
+        Some(path) => match Scenario::load(path) {
+            Ok(scenario) => scenario,
+            Err(error) => {
+                eprintln!("❌ {}: {}", path, error);
+                std::process::exit(1);
+            }
+        },
+        None => Scenario::default(),
+    };
+
+    if headless {
+        let Some(time_limit) = scenario.stop_conditions.time_limit else {
+            eprintln!("❌ {}: une limite de temps est nécessaire sans fenêtre", scenario.name);
+            std::process::exit(1);
+        };
+        let mut app = headless_app(scenario);
+        let status = run_mission(&mut app, time_limit);
+        std::process::exit(status.exit_code());
+    }
+
     App::new()
         .insert_resource(ClearColor(Color::BLACK))
         .add_plugins(DefaultPlugins.set(WindowPlugin {
@@ -13,6 +39,11 @@ fn main() {
             }),
             ..default()
         }))
-        .add_plugins(robots::SimulationPlugin)
+        .add_plugins(robots::SimulationPlugin::new(scenario))
+        .add_plugins(camera::CameraControlPlugin)
+        .add_plugins(clock::ClockControlPlugin)
+        .add_plugins(editor::MapEditorPlugin)
+        .add_plugins(inspector::InspectorPlugin)
+        .add_plugins(manual::ManualControlPlugin)
         .run();
-}
\ No newline at end of file
+}
@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+
+use crate::camera::SelectedRobot;
+use crate::clock::SimulationClock;
+use crate::daylight::CHARGING_DISTANCE;
+use crate::motion::{drive, Kinematics};
+use crate::pathfinding::PlannedPath;
+use crate::robots::{
+    load_cargo, nearest_base, unload_cargo, Base, Cargo, CollectionState, Deposit, DiscoveredResource, GameMap, Miner,
+    Resource, RobotBody, Station,
+};
+use crate::scenario::Timers;
+use crate::spatial::{SpatialIndex, SpatialKind};
+use crate::state::{set_state, RobotState, StateTransition, ARRIVAL_DISTANCE};
+use crate::stats::SimulationStats;
+use crate::watchdog::StuckWatchdog;
+
+// Robot piloté au clavier: les stratégies et la surveillance de blocage l'ignorent
+#[derive(Component, Debug)]
+pub struct ManuallyControlled;
+
+#[derive(Resource, Debug, Default)]
+pub struct ManualControl {
+    pub entity: Option<Entity>,
+    pub direction: Vec2,
+}
+
+// Pose ou retire le marqueur selon ManualControl; le robot relâché repart de zéro avec sa stratégie
+pub fn apply_manual_control(
+    mut commands: Commands,
+    control: Res<ManualControl>,
+    mut controlled_query: Query<
+        (Entity, &Transform, &mut PlannedPath, &mut StuckWatchdog, &mut Kinematics),
+        With<ManuallyControlled>,
+    >,
+    robots_query: Query<(), With<RobotBody>>,
+) {
+    if !control.is_changed() {
+        return;
+    }
+
+    for (entity, transform, mut path, mut watchdog, mut kinematics) in controlled_query.iter_mut() {
+        if control.entity == Some(entity) {
+            continue;
+        }
+        commands.entity(entity).remove::<ManuallyControlled>();
+        path.clear();
+        watchdog.reset(transform.translation.truncate());
+        kinematics.speed = 0.0;
+        println!("🤖 Robot {:?} rendu à sa stratégie", entity);
+    }
+
+    if let Some(entity) = control.entity {
+        if robots_query.contains(entity) && !controlled_query.contains(entity) {
+            commands.entity(entity).insert(ManuallyControlled);
+            println!("🎮 Robot {:?} piloté manuellement", entity);
+        }
+    }
+}
+
+pub fn drive_manual_robots(
+    control: Res<ManualControl>,
+    clock: Res<SimulationClock>,
+    game_map: Res<GameMap>,
+    mut robots_query: Query<(&mut Transform, &mut Kinematics, &RobotBody), With<ManuallyControlled>>,
+) {
+    let desired = (control.direction != Vec2::ZERO).then_some(control.direction);
+
+    for (mut transform, mut kinematics, body) in robots_query.iter_mut() {
+        let max_speed = kinematics.config.max_speed;
+        drive(&mut transform, &mut kinematics, desired, max_speed, body.radius, clock.delta_seconds(), &game_map);
+    }
+}
+
+// Un mineur piloté charge quand il s'arrête sur un gisement connu et décharge quand il s'arrête à une base
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn operate_manual_miners(
+    mut commands: Commands,
+    control: Res<ManualControl>,
+    clock: Res<SimulationClock>,
+    timers: Res<Timers>,
+    spatial_index: Res<SpatialIndex>,
+    mut miners_query: Query<(Entity, &Transform, &mut Cargo, &mut RobotState), (With<Miner>, With<ManuallyControlled>)>,
+    base_query: Query<&Transform, With<Base>>,
+    mut deposits_query: Query<(&Resource, &mut Deposit)>,
+    mut discovered_resource: ResMut<DiscoveredResource>,
+    mut collection_state: ResMut<CollectionState>,
+    mut station: ResMut<Station>,
+    mut stats: ResMut<SimulationStats>,
+    mut transitions: EventWriter<StateTransition>,
+) {
+    let bases: Vec<Vec2> = base_query.iter().map(|transform| transform.translation.truncate()).collect();
+
+    for (entity, transform, mut cargo, mut state) in miners_query.iter_mut() {
+        if matches!(*state, RobotState::Repairing { .. }) {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        // En mouvement, l'état ne garde aucune cible: le mineur rentre s'il est chargé, sinon il attend
+        if control.direction != Vec2::ZERO {
+            let next = if cargo.amount > 0 { RobotState::Returning } else { RobotState::Idle };
+            if state.can_transition_to(&next) {
+                set_state(entity, &mut state, next, &mut transitions);
+            }
+            continue;
+        }
+
+        if cargo.amount > 0 {
+            if position.distance(nearest_base(position, &bases)) < CHARGING_DISTANCE {
+                unload_cargo(entity, &mut cargo, &mut station, &mut stats);
+                set_state(entity, &mut state, RobotState::Unloading, &mut transitions);
+            }
+            continue;
+        }
+
+        // Seuls les gisements déjà signalés à la station peuvent être exploités
+        let deposit = spatial_index
+            .nearest(position, ARRIVAL_DISTANCE, SpatialKind::Resource)
+            .filter(|(deposit_entity, _, _)| station.knows_deposit(*deposit_entity))
+            .and_then(|(deposit_entity, deposit_position, _)| {
+                let (resource, deposit) = deposits_query.get_mut(deposit_entity).ok()?;
+                (!deposit.is_exhausted()).then_some((deposit_entity, deposit_position, *resource, deposit))
+            });
+        let Some((deposit_entity, deposit_position, resource, mut deposit)) = deposit else {
+            if *state != RobotState::Idle && state.can_transition_to(&RobotState::Idle) {
+                set_state(entity, &mut state, RobotState::Idle, &mut transitions);
+            }
+            continue;
+        };
+
+        let progress = match *state {
+            RobotState::Collecting { progress } => progress,
+            _ => {
+                println!("⏱️ Début de la collecte pour le mineur {:?}", entity);
+                set_state(entity, &mut state, RobotState::MovingTo(deposit_position), &mut transitions);
+                0.0
+            }
+        } + clock.delta_seconds() / timers.collection_time;
+        if progress < 1.0 {
+            set_state(entity, &mut state, RobotState::Collecting { progress }, &mut transitions);
+            continue;
+        }
+
+        let exhausted = load_cargo(
+            &mut commands,
+            entity,
+            &mut cargo,
+            deposit_entity,
+            resource,
+            &mut deposit,
+            &mut station,
+            &mut stats,
+        );
+        if exhausted && collection_state.resource_entity == Some(deposit_entity) {
+            collection_state.release(&mut discovered_resource);
+        }
+        set_state(entity, &mut state, RobotState::Returning, &mut transitions);
+    }
+}
+
+pub struct ManualControlPlugin;
+
+impl Plugin for ManualControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, read_manual_keys);
+    }
+}
+
+fn read_manual_keys(keyboard: Res<Input<KeyCode>>, selected: Res<SelectedRobot>, mut control: ResMut<ManualControl>) {
+    if keyboard.just_pressed(KeyCode::M) {
+        control.entity = match control.entity {
+            Some(_) => None,
+            None => selected.0,
+        };
+    }
+
+    if control.entity.is_none() {
+        return;
+    }
+
+    let mut direction = Vec2::ZERO;
+    for (keys, step) in [
+        ([KeyCode::Left, KeyCode::A], Vec2::NEG_X),
+        ([KeyCode::Right, KeyCode::D], Vec2::X),
+        ([KeyCode::Up, KeyCode::W], Vec2::Y),
+        ([KeyCode::Down, KeyCode::S], Vec2::NEG_Y),
+    ] {
+        if keyboard.any_pressed(keys) {
+            direction += step;
+        }
+    }
+    if control.direction != direction {
+        control.direction = direction;
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+
+use crate::collision::slide_circle;
+use crate::robots::GameMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MotionConfig {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub max_turn_rate: f32,
+}
+
+impl MotionConfig {
+    pub const EXPLORER: MotionConfig = MotionConfig {
+        max_speed: 100.0,
+        acceleration: 200.0,
+        max_turn_rate: 4.0,
+    };
+
+    pub const MINER: MotionConfig = MotionConfig {
+        max_speed: 120.0,
+        acceleration: 240.0,
+        max_turn_rate: 5.0,
+    };
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Kinematics {
+    pub config: MotionConfig,
+    pub speed: f32,
+    pub heading: f32,
+}
+
+impl Kinematics {
+    pub fn new(config: MotionConfig) -> Self {
+        Self {
+            config,
+            speed: 0.0,
+            heading: 0.0,
+        }
+    }
+
+    pub fn direction(&self) -> Vec2 {
+        Vec2::from_angle(self.heading)
+    }
+
+    // Même convention que le reste de la simulation: Quat::from_rotation_z(-angle)
+    pub fn rotation(&self) -> Quat {
+        Quat::from_rotation_z(-self.heading)
+    }
+
+    // Tourne vers `desired` au plus à max_turn_rate et accélère au plus à `acceleration`;
+    // comme un robot à roues différentielles, il ralentit tant qu'il n'est pas aligné
+    pub fn steer(&mut self, desired: Option<Vec2>, target_speed: f32, delta: f32) -> Vec2 {
+        let target_speed = match desired.and_then(|direction| direction.try_normalize()) {
+            Some(direction) => {
+                let error = wrap_angle(direction.y.atan2(direction.x) - self.heading);
+                let max_turn = self.config.max_turn_rate * delta;
+                self.heading = wrap_angle(self.heading + error.clamp(-max_turn, max_turn));
+
+                let remaining = wrap_angle(direction.y.atan2(direction.x) - self.heading);
+                target_speed.min(self.config.max_speed) * remaining.cos().max(0.0)
+            }
+            None => 0.0,
+        };
+
+        let max_change = self.config.acceleration * delta;
+        self.speed += (target_speed - self.speed).clamp(-max_change, max_change);
+        self.direction() * self.speed * delta
+    }
+
+    // Vitesse qui permet encore de s'arrêter sur `distance`
+    pub fn braking_speed(&self, distance: f32) -> f32 {
+        (2.0 * self.config.acceleration * distance.max(0.0)).sqrt().min(self.config.max_speed)
+    }
+}
+
+pub fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+// Applique la commande au robot; un choc contre un obstacle fait perdre la vitesse
+pub fn drive(
+    transform: &mut Transform,
+    kinematics: &mut Kinematics,
+    desired: Option<Vec2>,
+    target_speed: f32,
+    radius: f32,
+    delta: f32,
+    game_map: &GameMap,
+) {
+    let start = transform.translation.truncate();
+    let motion = kinematics.steer(desired, target_speed, delta);
+    let new_pos = slide_circle(start, start + motion, radius, game_map);
+
+    if delta > 0.0 && new_pos.distance(start) < motion.length() - 0.01 {
+        kinematics.speed = (new_pos - start).dot(kinematics.direction()).max(0.0) / delta;
+    }
+
+    transform.translation.x = new_pos.x;
+    transform.translation.y = new_pos.y;
+    transform.rotation = kinematics.rotation();
+}
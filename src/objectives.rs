@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+
+use crate::robots::{ExploredZones, GameMap};
+use crate::scenario::{Objective, StopConditions};
+use crate::sensor::{heading_from_rotation, Sensor};
+use crate::stats::SimulationStats;
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct ScientificSite {
+    pub analyzed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissionOutcome {
+    Success,
+    Failure(String),
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MissionStatus {
+    pub outcome: Option<MissionOutcome>,
+    pub ended_at: Option<f32>,
+}
+
+impl MissionStatus {
+    pub fn is_running(&self) -> bool {
+        self.outcome.is_none()
+    }
+
+    // Code de sortie des exécutions sans fenêtre: 0 réussite, 2 échec, 3 mission non terminée
+    pub fn exit_code(&self) -> i32 {
+        match self.outcome {
+            Some(MissionOutcome::Success) => 0,
+            Some(MissionOutcome::Failure(_)) => 2,
+            None => 3,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct MissionEnded {
+    pub outcome: MissionOutcome,
+    pub elapsed: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectiveProgress {
+    pub objective: Objective,
+    pub current: f32,
+    pub target: f32,
+}
+
+impl ObjectiveProgress {
+    pub fn is_met(&self) -> bool {
+        self.current >= self.target
+    }
+}
+
+pub fn objective_progress(
+    objective: &Objective,
+    stats: &SimulationStats,
+    explored_ratio: f32,
+    sites_total: u32,
+) -> ObjectiveProgress {
+    let (current, target) = match objective {
+        Objective::CollectMinerals(count) => (stats.minerals_collected as f32, *count as f32),
+        Objective::CollectEnergy(count) => (stats.energy_collected as f32, *count as f32),
+        Objective::ExploreRatio(ratio) => (explored_ratio, *ratio),
+        Objective::AnalyzeScientificSites => (stats.sites_analyzed as f32, sites_total as f32),
+    };
+    ObjectiveProgress {
+        objective: objective.clone(),
+        current,
+        target,
+    }
+}
+
+pub fn mission_running(status: Res<MissionStatus>) -> bool {
+    status.is_running()
+}
+
+pub fn analyze_scientific_sites(
+    sensors_query: Query<(&Transform, &Sensor)>,
+    mut sites_query: Query<(&Transform, &mut ScientificSite, &mut Sprite)>,
+    game_map: Res<GameMap>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    for (site_transform, mut site, mut sprite) in sites_query.iter_mut() {
+        if site.analyzed {
+            continue;
+        }
+        let site_pos = site_transform.translation.truncate();
+
+        let seen = sensors_query.iter().any(|(transform, sensor)| {
+            let origin = transform.translation.truncate();
+            sensor.can_see(origin, heading_from_rotation(transform.rotation), site_pos, &game_map)
+        });
+        if seen {
+            println!("🔬 Site scientifique analysé à {:?}", site_pos);
+            site.analyzed = true;
+            sprite.color = Color::rgb(0.5, 0.2, 0.5);
+            stats.sites_analyzed += 1;
+        }
+    }
+}
+
+// Vérifie à chaque pas les objectifs du scénario et la limite de temps
+pub fn evaluate_objectives(
+    stop_conditions: Res<StopConditions>,
+    stats: Res<SimulationStats>,
+    explored_zones: Res<ExploredZones>,
+    sites_query: Query<&ScientificSite>,
+    mut status: ResMut<MissionStatus>,
+    mut ended_events: EventWriter<MissionEnded>,
+) {
+    if !status.is_running() {
+        return;
+    }
+
+    let explored_ratio = explored_zones.explored_ratio();
+    let sites_total = sites_query.iter().count() as u32;
+    let progress: Vec<ObjectiveProgress> = stop_conditions
+        .objectives
+        .iter()
+        .map(|objective| objective_progress(objective, &stats, explored_ratio, sites_total))
+        .collect();
+
+    let time_up = stop_conditions.time_limit.is_some_and(|limit| stats.elapsed >= limit);
+    let outcome = if !progress.is_empty() && progress.iter().all(ObjectiveProgress::is_met) {
+        MissionOutcome::Success
+    } else if time_up {
+        let missed: Vec<String> = progress
+            .iter()
+            .filter(|entry| !entry.is_met())
+            .map(|entry| format!("{:?}", entry.objective))
+            .collect();
+        if missed.is_empty() {
+            MissionOutcome::Success
+        } else {
+            MissionOutcome::Failure(format!("temps écoulé, objectifs manqués: {}", missed.join(", ")))
+        }
+    } else {
+        return;
+    };
+
+    status.outcome = Some(outcome.clone());
+    status.ended_at = Some(stats.elapsed);
+    ended_events.send(MissionEnded {
+        outcome,
+        elapsed: stats.elapsed,
+    });
+}
+
+pub fn print_final_report(
+    mut ended_events: EventReader<MissionEnded>,
+    stop_conditions: Res<StopConditions>,
+    stats: Res<SimulationStats>,
+    explored_zones: Res<ExploredZones>,
+    sites_query: Query<&ScientificSite>,
+) {
+    for event in ended_events.iter() {
+        match &event.outcome {
+            MissionOutcome::Success => println!("🏁 Mission réussie en {:.1}s", event.elapsed),
+            MissionOutcome::Failure(reason) => println!("💥 Mission échouée après {:.1}s: {}", event.elapsed, reason),
+        }
+
+        let explored_ratio = explored_zones.explored_ratio();
+        let sites_total = sites_query.iter().count() as u32;
+        for objective in &stop_conditions.objectives {
+            let progress = objective_progress(objective, &stats, explored_ratio, sites_total);
+            println!(
+                "   {} {:?}: {:.2} / {:.2}",
+                if progress.is_met() { "✅" } else { "❌" },
+                progress.objective,
+                progress.current,
+                progress.target
+            );
+        }
+        println!(
+            "📊 Découvertes: {}, énergie: {}, minerais: {}, sites analysés: {}, carte explorée: {:.0}%, blocages: {}",
+            stats.discoveries,
+            stats.energy_collected,
+            stats.minerals_collected,
+            stats.sites_analyzed,
+            explored_ratio * 100.0,
+            stats.stuck_events
+        );
+        if stats.robots_built > 0 || stats.outposts_built > 0 {
+            println!(
+                "🏗️ Robots produits: {}, avant-postes construits: {}",
+                stats.robots_built, stats.outposts_built
+            );
+        }
+        if stats.failures > 0 {
+            println!("⚡ Pannes: {}, réparations: {}", stats.failures, stats.repairs);
+        }
+        if stats.batteries_depleted > 0 {
+            println!("🪫 Batteries à plat: {}", stats.batteries_depleted);
+        }
+        if stats.script_errors > 0 {
+            println!("📜 Erreurs de script: {}", stats.script_errors);
+        }
+        if stats.messages_sent > 0 {
+            println!(
+                "📡 Messages radio: {} envoyés, {} reçus, {} perdus",
+                stats.messages_sent, stats.messages_delivered, stats.messages_lost
+            );
+        }
+    }
+}
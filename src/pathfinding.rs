@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::collision::{circle_overlaps_obstacles, first_hit};
+use crate::robots::GameMap;
+
+const WAYPOINT_REACHED: f32 = 10.0;
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct PlannedPath {
+    pub goal: Option<Vec2>,
+    pub waypoints: Vec<Vec2>,
+}
+
+impl PlannedPath {
+    pub fn set(&mut self, goal: Vec2, waypoints: Vec<Vec2>) {
+        self.goal = Some(goal);
+        self.waypoints = waypoints;
+    }
+
+    pub fn clear(&mut self) {
+        self.goal = None;
+        self.waypoints.clear();
+    }
+
+    // Prochain point de passage vers `goal`; le chemin est oublié dès que l'objectif change
+    pub fn next_waypoint(&mut self, position: Vec2, goal: Vec2) -> Vec2 {
+        if self.goal != Some(goal) {
+            self.clear();
+            return goal;
+        }
+
+        while let Some(&waypoint) = self.waypoints.first() {
+            if waypoint.distance(position) > WAYPOINT_REACHED {
+                break;
+            }
+            self.waypoints.remove(0);
+        }
+
+        self.waypoints.first().copied().unwrap_or(goal)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct OpenCell {
+    cost: f32,
+    cell: IVec2,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| (self.cell.x, self.cell.y).cmp(&(other.cell.x, other.cell.y)))
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// A* sur la grille (8 voisins, sans couper les coins) puis lissage des points de passage
+pub fn find_path(from: Vec2, to: Vec2, radius: f32, game_map: &GameMap) -> Option<Vec<Vec2>> {
+    let start = game_map.world_to_cell(from);
+    let goal = game_map.world_to_cell(to);
+    if !game_map.in_bounds(start) || !game_map.in_bounds(goal) {
+        return None;
+    }
+
+    let passable = |cell: IVec2| {
+        cell == start
+            || cell == goal
+            || (!game_map.is_obstacle_cell(cell)
+                && !circle_overlaps_obstacles(game_map.cell_center(cell), radius, game_map))
+    };
+    let heuristic = |cell: IVec2| (goal - cell).as_vec2().length();
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+    let mut costs: HashMap<IVec2, f32> = HashMap::new();
+    costs.insert(start, 0.0);
+    open.push(OpenCell { cost: heuristic(start), cell: start });
+
+    while let Some(OpenCell { cell, .. }) = open.pop() {
+        if cell == goal {
+            let mut cells = vec![cell];
+            let mut current = cell;
+            while let Some(&previous) = came_from.get(&current) {
+                cells.push(previous);
+                current = previous;
+            }
+            cells.reverse();
+            return Some(smooth_path(from, to, &cells, radius, game_map));
+        }
+
+        let cost = costs[&cell];
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let next = cell + IVec2::new(dx, dy);
+                if !passable(next) {
+                    continue;
+                }
+                if dx != 0 && dy != 0
+                    && (game_map.is_obstacle_cell(cell + IVec2::new(dx, 0))
+                        || game_map.is_obstacle_cell(cell + IVec2::new(0, dy)))
+                {
+                    continue;
+                }
+
+                let next_cost = cost + if dx != 0 && dy != 0 { std::f32::consts::SQRT_2 } else { 1.0 };
+                if costs.get(&next).is_none_or(|&known| next_cost < known) {
+                    costs.insert(next, next_cost);
+                    came_from.insert(next, cell);
+                    open.push(OpenCell { cost: next_cost + heuristic(next), cell: next });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Ne garde que les points de passage nécessaires: le cercle doit pouvoir aller en ligne droite
+fn smooth_path(from: Vec2, to: Vec2, cells: &[IVec2], radius: f32, game_map: &GameMap) -> Vec<Vec2> {
+    let mut points: Vec<Vec2> = cells.iter().map(|&cell| game_map.cell_center(cell)).collect();
+    if let Some(last) = points.last_mut() {
+        *last = to;
+    }
+
+    let is_clear = |a: Vec2, b: Vec2| first_hit(a, b - a, radius, game_map).is_none();
+
+    let mut waypoints = Vec::new();
+    let mut anchor = from;
+    let mut index = 0;
+    while index < points.len() {
+        let furthest = (index..points.len())
+            .rev()
+            .find(|&candidate| is_clear(anchor, points[candidate]))
+            .unwrap_or(index);
+        anchor = points[furthest];
+        waypoints.push(anchor);
+        index = furthest + 1;
+    }
+    waypoints
+}
@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::clock::SimulationClock;
+use crate::robots::{spawn_robot, Explorer, Miner, Station};
+use crate::scenario::{RobotKind, RobotSpec, Scenario, Timers};
+use crate::stats::SimulationStats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RobotCost {
+    pub energy: u32,
+    pub minerals: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProductionPolicy {
+    #[default]
+    Disabled,
+    // Garde la flotte au plus près de `explorers` explorateurs pour `miners` mineurs
+    KeepRatio { explorers: u32, miners: u32 },
+    // Construit `kind` tant que les stocks dépassent les seuils
+    StockThreshold { kind: RobotKind, energy: u32, minerals: u32 },
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProductionConfig {
+    pub policy: ProductionPolicy,
+    pub explorer_cost: RobotCost,
+    pub miner_cost: RobotCost,
+    pub build_time: f32,
+    pub queue_limit: usize,
+    pub max_fleet: u32,
+}
+
+impl Default for ProductionConfig {
+    fn default() -> Self {
+        Self {
+            policy: ProductionPolicy::Disabled,
+            explorer_cost: RobotCost { energy: 8, minerals: 6 },
+            miner_cost: RobotCost { energy: 4, minerals: 5 },
+            build_time: 10.0,
+            queue_limit: 2,
+            max_fleet: 12,
+        }
+    }
+}
+
+impl ProductionConfig {
+    pub fn cost(&self, kind: RobotKind) -> RobotCost {
+        match kind {
+            RobotKind::Explorer => self.explorer_cost,
+            RobotKind::Miner => self.miner_cost,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProductionOrder {
+    pub kind: RobotKind,
+    pub remaining: f32,
+}
+
+// Chaîne de production unique: seul le premier robot de la file avance
+#[derive(Resource, Debug, Default)]
+pub struct ProductionQueue {
+    pub orders: VecDeque<ProductionOrder>,
+}
+
+impl ProductionQueue {
+    pub fn count(&self, kind: RobotKind) -> u32 {
+        self.orders.iter().filter(|order| order.kind == kind).count() as u32
+    }
+}
+
+// Type du prochain robot à lancer selon la politique; `explorers` et `miners` comptent aussi la file
+pub fn next_robot(config: &ProductionConfig, explorers: u32, miners: u32, station: &Station) -> Option<RobotKind> {
+    if explorers + miners >= config.max_fleet {
+        return None;
+    }
+
+    let kind = match config.policy {
+        ProductionPolicy::Disabled => return None,
+        ProductionPolicy::KeepRatio { explorers: wanted_explorers, miners: wanted_miners } => {
+            // Un type demandé à zéro n'est jamais construit
+            if wanted_miners == 0 {
+                RobotKind::Explorer
+            } else if wanted_explorers == 0 {
+                RobotKind::Miner
+            } else if explorers * wanted_miners < miners * wanted_explorers {
+                RobotKind::Explorer
+            } else {
+                RobotKind::Miner
+            }
+        }
+        ProductionPolicy::StockThreshold { kind, energy, minerals } => {
+            if station.energy < energy || station.minerals < minerals {
+                return None;
+            }
+            kind
+        }
+    };
+
+    let cost = config.cost(kind);
+    (station.energy >= cost.energy && station.minerals >= cost.minerals).then_some(kind)
+}
+
+pub fn plan_production(
+    config: Res<ProductionConfig>,
+    mut queue: ResMut<ProductionQueue>,
+    mut station: ResMut<Station>,
+    explorers_query: Query<(), With<Explorer>>,
+    miners_query: Query<(), With<Miner>>,
+) {
+    if queue.orders.len() >= config.queue_limit {
+        return;
+    }
+
+    let explorers = explorers_query.iter().count() as u32 + queue.count(RobotKind::Explorer);
+    let miners = miners_query.iter().count() as u32 + queue.count(RobotKind::Miner);
+    let Some(kind) = next_robot(&config, explorers, miners, &station) else {
+        return;
+    };
+
+    let cost = config.cost(kind);
+    station.energy -= cost.energy;
+    station.minerals -= cost.minerals;
+    queue.orders.push_back(ProductionOrder {
+        kind,
+        remaining: config.build_time,
+    });
+    println!(
+        "🏭 Production lancée: {:?} ({} énergie, {} minerais, {} en file)",
+        kind, cost.energy, cost.minerals, queue.orders.len()
+    );
+}
+
+pub fn advance_production(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    scenario: Res<Scenario>,
+    timers: Res<Timers>,
+    mut queue: ResMut<ProductionQueue>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    let Some(order) = queue.orders.front_mut() else {
+        return;
+    };
+    order.remaining -= clock.delta_seconds();
+    if order.remaining > 0.0 {
+        return;
+    }
+
+    let kind = order.kind;
+    queue.orders.pop_front();
+
+    // Le nouveau robot reprend la configuration du premier groupe de son type dans le scénario
+    let spec = scenario
+        .robots
+        .iter()
+        .find(|spec| spec.kind == kind)
+        .cloned()
+        .unwrap_or(RobotSpec {
+            kind,
+            position: (0.0, 0.0),
+            count: 1,
+            spacing: (0.0, 0.0),
+            radius: None,
+            motion: None,
+            sensor: None,
+        });
+    let base = scenario.base_positions()[0];
+    let position = base + Vec2::new(0.0, -25.0);
+    let entity = spawn_robot(&mut commands, &spec, &position, &timers);
+    stats.robots_built += 1;
+    println!("🤖 Nouveau {:?} {:?} sorti de la base {:?}", kind, entity, base);
+}
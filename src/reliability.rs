@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::SimulationClock;
+use crate::manual::ManuallyControlled;
+use crate::motion::Kinematics;
+use crate::robots::{nearest_base, Base, Miner, SimulationRng, Station};
+use crate::sensor::Sensor;
+use crate::state::{set_state, RobotState, StateTransition};
+use crate::stats::SimulationStats;
+
+pub const REPAIR_DISTANCE: f32 = 40.0;
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReliabilityConfig {
+    // Usure gagnée par pixel parcouru, de 0 (neuf) à 1 (usé)
+    pub wear_per_distance: f32,
+    // Probabilité de panne par seconde pour un robot complètement usé; 0 désactive les pannes
+    pub failure_rate: f64,
+    // Facteur de vitesse d'une propulsion dégradée
+    pub degraded_speed: f32,
+    pub repair_cost: u32,
+    pub repair_time: f32,
+}
+
+impl Default for ReliabilityConfig {
+    fn default() -> Self {
+        Self {
+            wear_per_distance: 0.0005,
+            failure_rate: 0.0,
+            degraded_speed: 0.4,
+            repair_cost: 3,
+            repair_time: 6.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    SensorDark,
+    DriveDegraded,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct Reliability {
+    pub wear: f32,
+    pub faults: Vec<Fault>,
+    // Vrai tant que la station peut payer la réparation; sinon le robot continue en mode dégradé
+    pub repair_requested: bool,
+    last_position: Option<Vec2>,
+    nominal_sensor_range: Option<f32>,
+    nominal_max_speed: Option<f32>,
+}
+
+impl Reliability {
+    pub fn needs_repair(&self) -> bool {
+        !self.faults.is_empty()
+    }
+
+    // Ajoute l'usure du trajet depuis la dernière position connue
+    pub fn record_travel(&mut self, position: Vec2, wear_per_distance: f32) {
+        if let Some(last) = self.last_position {
+            self.wear = (self.wear + last.distance(position) * wear_per_distance).min(1.0);
+        }
+        self.last_position = Some(position);
+    }
+
+    pub fn apply_fault(&mut self, fault: Fault, sensor: Option<&mut Sensor>, kinematics: &mut Kinematics, degraded_speed: f32) {
+        match fault {
+            Fault::SensorDark => {
+                if let Some(sensor) = sensor {
+                    self.nominal_sensor_range = Some(sensor.range);
+                    sensor.range = 0.0;
+                }
+            }
+            Fault::DriveDegraded => {
+                self.nominal_max_speed = Some(kinematics.config.max_speed);
+                kinematics.config.max_speed *= degraded_speed;
+            }
+        }
+        self.faults.push(fault);
+    }
+
+    pub fn repair(&mut self, sensor: Option<&mut Sensor>, kinematics: &mut Kinematics) {
+        if let (Some(range), Some(sensor)) = (self.nominal_sensor_range.take(), sensor) {
+            sensor.range = range;
+        }
+        if let Some(max_speed) = self.nominal_max_speed.take() {
+            kinematics.config.max_speed = max_speed;
+        }
+        self.faults.clear();
+        self.wear = 0.0;
+        self.repair_requested = false;
+    }
+}
+
+// Usure avec la distance parcourue et pannes tirées avec la graine de la simulation
+pub fn wear_and_failures(
+    config: Res<ReliabilityConfig>,
+    clock: Res<SimulationClock>,
+    mut rng: ResMut<SimulationRng>,
+    mut stats: ResMut<SimulationStats>,
+    mut robots_query: Query<(Entity, &Transform, &mut Reliability, &mut Kinematics, Option<&mut Sensor>)>,
+) {
+    for (entity, transform, mut reliability, mut kinematics, mut sensor) in robots_query.iter_mut() {
+        reliability.record_travel(transform.translation.truncate(), config.wear_per_distance);
+
+        let probability = (config.failure_rate * reliability.wear as f64 * clock.delta_seconds() as f64).min(1.0);
+        if probability <= 0.0 || !rng.0.gen_bool(probability) {
+            continue;
+        }
+
+        let candidates: Vec<Fault> = [Fault::SensorDark, Fault::DriveDegraded]
+            .into_iter()
+            .filter(|fault| *fault != Fault::SensorDark || sensor.is_some())
+            .filter(|fault| !reliability.faults.contains(fault))
+            .collect();
+        let Some(&fault) = candidates.choose(&mut rng.0) else {
+            continue;
+        };
+
+        reliability.apply_fault(fault, sensor.as_deref_mut(), &mut kinematics, config.degraded_speed);
+        stats.failures += 1;
+        println!("⚡ Panne {:?} sur le robot {:?} (usure {:.0}%)", fault, entity, reliability.wear * 100.0);
+    }
+}
+
+// Un robot en panne abandonne sa tâche, rentre à la base et s'y fait réparer contre des minerais;
+// tant que la station ne peut pas payer, il continue de travailler en mode dégradé
+#[allow(clippy::type_complexity)]
+pub fn repair_robots(
+    config: Res<ReliabilityConfig>,
+    clock: Res<SimulationClock>,
+    mut station: ResMut<Station>,
+    mut stats: ResMut<SimulationStats>,
+    mut transitions: EventWriter<StateTransition>,
+    mut robots_query: Query<
+        (Entity, &Transform, &mut RobotState, &mut Reliability, &mut Kinematics, Option<&mut Sensor>, Option<&Miner>),
+        Without<ManuallyControlled>,
+    >,
+    bases_query: Query<&Transform, With<Base>>,
+) {
+    let bases: Vec<Vec2> = bases_query.iter().map(|transform| transform.translation.truncate()).collect();
+
+    for (entity, transform, mut state, mut reliability, mut kinematics, mut sensor, miner) in robots_query.iter_mut() {
+        if let RobotState::Repairing { progress } = *state {
+            kinematics.speed = 0.0;
+            let progress = progress + clock.delta_seconds() / config.repair_time;
+            if progress < 1.0 {
+                set_state(entity, &mut state, RobotState::Repairing { progress }, &mut transitions);
+                continue;
+            }
+            reliability.repair(sensor.as_deref_mut(), &mut kinematics);
+            stats.repairs += 1;
+            println!("✅ Robot {:?} réparé", entity);
+            set_state(entity, &mut state, RobotState::Idle, &mut transitions);
+            continue;
+        }
+
+        reliability.repair_requested = reliability.needs_repair() && station.minerals >= config.repair_cost;
+        if !reliability.repair_requested {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let at_base = position.distance(nearest_base(position, &bases)) < REPAIR_DISTANCE;
+        match *state {
+            RobotState::Idle | RobotState::Unloading | RobotState::Returning if at_base => {
+                station.minerals -= config.repair_cost;
+                kinematics.speed = 0.0;
+                println!("🔧 Réparation du robot {:?} ({} minerais)", entity, config.repair_cost);
+                set_state(entity, &mut state, RobotState::Repairing { progress: 0.0 }, &mut transitions);
+            }
+            // Les explorateurs rentrent d'eux-mêmes; un mineur quitte le gisement, qui reste dans CollectionState
+            // pour les autres mineurs jusqu'à épuisement
+            RobotState::Idle | RobotState::MovingTo(_) | RobotState::Collecting { .. } if miner.is_some() => {
+                station.claims.remove(&entity);
+                println!("🔁 Mineur {:?} en panne, il rentre se faire réparer", entity);
+                set_state(entity, &mut state, RobotState::Returning, &mut transitions);
+            }
+            _ => {}
+        }
+    }
+}
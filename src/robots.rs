@@ -1,32 +1,154 @@
 use bevy::prelude::*;
 use rand::prelude::*;
 use noise::{NoiseFn, Perlin};
-use bevy::ecs::system::ParamSet;
-use std::collections::{BinaryHeap, HashMap};
-use ordered_float::OrderedFloat;
-use bevy::time::TimerMode;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
-#[derive(Component)]
-struct Explorer;
+use crate::behavior::{attach_behavior_trees, run_behavior_trees, BehaviorTree};
+use crate::clock::{run_simulation_ticks, SimulationClock, SimulationTick};
+use crate::collision::{circle_overlaps_obstacles, slide_circle};
+use crate::comms::{
+    announce_task_claims, deliver_messages, share_map_patches, transmit_messages, CommsConfig, Network, Payload, Radio,
+};
+use crate::construction::{build_outposts, OutpostOrders};
+use crate::daylight::{
+    spawn_night_overlay, update_batteries, update_night_overlay, Battery, DaylightConfig, CHARGING_DISTANCE,
+};
+use crate::environment::{
+    react_to_environment, trigger_environment_events, update_storms, EnvironmentClock, EnvironmentEvent,
+};
+use crate::manual::{apply_manual_control, drive_manual_robots, operate_manual_miners, ManualControl, ManuallyControlled};
+use crate::motion::{drive, Kinematics};
+use crate::objectives::{
+    analyze_scientific_sites, evaluate_objectives, mission_running, print_final_report, MissionEnded,
+    MissionStatus, ScientificSite,
+};
+use crate::pathfinding::PlannedPath;
+use crate::production::{advance_production, plan_production, ProductionQueue};
+use crate::reliability::{repair_robots, wear_and_failures, Reliability};
+use crate::scenario::{RobotKind, RobotSpec, Scenario, Timers};
+use crate::scripting::{attach_robot_scripts, run_robot_scripts, RobotScript};
+use crate::sensor::{heading_from_rotation, Sensor};
+use crate::state::{
+    check_state_invariants, miner_step, set_state, MinerAction, MinerSituation, RobotState,
+    StateTransition,
+};
+use crate::spatial::{sync_spatial_index, SpatialIndex, SpatialKind};
+use crate::stats::{update_stats_clock, SimulationStats};
+use crate::watchdog::{detect_stuck_robots, recover_stuck_robots, RobotStuck, StuckSettings, StuckWatchdog};
 
 #[derive(Component)]
-struct Miner;
+pub struct Explorer;
 
 #[derive(Component)]
-struct Base;
+pub struct Miner;
 
 #[derive(Component)]
-struct Crystal;
+pub struct Base;
 
-#[derive(Component)]
-struct IdleMiner;
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RobotBody {
+    pub radius: f32,
+}
 
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Resource {
     Energy,
     Mineral,
 }
 
+impl Resource {
+    pub fn initial_quantity(&self) -> u32 {
+        match self {
+            Resource::Energy => 6,
+            Resource::Mineral => 9,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Deposit {
+    pub remaining: u32,
+    pub initial: u32,
+}
+
+impl Deposit {
+    pub fn new(quantity: u32) -> Self {
+        Self {
+            remaining: quantity,
+            initial: quantity,
+        }
+    }
+
+    pub fn take(&mut self, amount: u32) -> u32 {
+        let taken = amount.min(self.remaining);
+        self.remaining -= taken;
+        taken
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    pub fn fill_ratio(&self) -> f32 {
+        self.remaining as f32 / self.initial.max(1) as f32
+    }
+}
+
+#[derive(Component, Debug, Default)]
+pub struct Cargo {
+    pub resource: Option<Resource>,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct KnownDeposit {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub resource: Resource,
+    pub remaining: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct Station {
+    pub energy: u32,
+    pub minerals: u32,
+    pub known_deposits: Vec<KnownDeposit>,
+    pub mapped_cells: HashSet<IVec2>,
+    pub claims: HashMap<Entity, Vec2>,
+}
+
+impl Station {
+    pub fn register_deposit(&mut self, entity: Entity, position: Vec2, resource: Resource, remaining: u32) {
+        if self.knows_deposit(entity) {
+            return;
+        }
+        self.known_deposits.push(KnownDeposit { entity, position, resource, remaining });
+    }
+
+    pub fn knows_deposit(&self, entity: Entity) -> bool {
+        self.known_deposits.iter().any(|deposit| deposit.entity == entity)
+    }
+
+    pub fn update_deposit(&mut self, entity: Entity, remaining: u32) {
+        if remaining == 0 {
+            self.known_deposits.retain(|deposit| deposit.entity != entity);
+        } else if let Some(deposit) = self.known_deposits.iter_mut().find(|deposit| deposit.entity == entity) {
+            deposit.remaining = remaining;
+        }
+    }
+
+    pub fn unload(&mut self, cargo: &mut Cargo) {
+        match cargo.resource {
+            Some(Resource::Energy) => self.energy += cargo.amount,
+            Some(Resource::Mineral) => self.minerals += cargo.amount,
+            None => {}
+        }
+        cargo.resource = None;
+        cargo.amount = 0;
+    }
+}
+
 #[derive(Component)]
 struct DebugGrid;
 
@@ -38,6 +160,25 @@ pub struct GameMap {
     pub seed: u32,
 }
 
+impl GameMap {
+    pub fn world_to_cell(&self, pos: Vec2) -> IVec2 {
+        ((pos + self.size / 2.0) / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn cell_center(&self, cell: IVec2) -> Vec2 {
+        (cell.as_vec2() + 0.5) * self.cell_size - self.size / 2.0
+    }
+
+    pub fn in_bounds(&self, cell: IVec2) -> bool {
+        cell.x >= 0 && cell.x < self.obstacles[0].len() as i32
+            && cell.y >= 0 && cell.y < self.obstacles.len() as i32
+    }
+
+    pub fn is_obstacle_cell(&self, cell: IVec2) -> bool {
+        !self.in_bounds(cell) || self.obstacles[cell.y as usize][cell.x as usize]
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct MapResources {
     pub energy_positions: Vec<Vec2>,
@@ -51,483 +192,793 @@ pub struct DiscoveredResource {
 }
 
 #[derive(Resource)]
-struct ExploredZones {
-    grid: Vec<Vec<bool>>,
-    cell_size: f32,
+pub struct ExploredZones {
+    pub grid: Vec<Vec<bool>>,
+    pub cell_size: f32,
 }
 
-impl Default for ExploredZones {
-    fn default() -> Self {
+impl ExploredZones {
+    pub fn for_map(game_map: &GameMap) -> Self {
         Self {
-            grid: vec![vec![false; 80]; 60],
-            cell_size: 10.0,
+            grid: vec![vec![false; game_map.obstacles[0].len()]; game_map.obstacles.len()],
+            cell_size: game_map.cell_size,
         }
     }
+
+    pub fn explored_ratio(&self) -> f32 {
+        let total = self.grid.len() * self.grid[0].len();
+        let explored = self.grid.iter().flatten().filter(|&&cell| cell).count();
+        explored as f32 / total as f32
+    }
 }
 
-#[derive(Resource)]
+#[derive(Component)]
 pub struct ExplorerState {
     pub current_direction: Vec2,
     pub time_until_change: f32,
 }
 
-#[derive(Component)]
-struct ReturningMiner;
-
 #[derive(Resource)]
-struct CollectionState {
-    timer: Timer,
-    resource_entity: Option<Entity>,
-    collecting: bool,
-    position: Option<Vec2>,
+pub struct SimulationRng(pub StdRng);
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExplorationMode {
+    Serialized,
+    #[default]
+    Concurrent,
 }
 
-impl Default for CollectionState {
-    fn default() -> Self {
-        Self {
-            timer: Timer::from_seconds(2.0, TimerMode::Once),
-            resource_entity: None,
-            collecting: false,
-            position: None,
+// Gisement en cours d'exploitation par les mineurs, partagé entre leurs stratégies
+#[derive(Resource, Default)]
+pub(crate) struct CollectionState {
+    pub(crate) resource_entity: Option<Entity>,
+    pub(crate) position: Option<Vec2>,
+}
+
+impl CollectionState {
+    // Suit la cible choisie par la station; une cible sans ressource à proximité est abandonnée
+    fn refresh(&mut self, discovered_resource: &mut DiscoveredResource, station: &mut Station, spatial_index: &SpatialIndex) {
+        if self.position != discovered_resource.position {
+            self.position = discovered_resource.position;
+            self.resource_entity = None;
         }
+        
+        let Some(target_pos) = self.position else {
+            return;
+        };
+        if self.resource_entity.is_none() {
+            self.resource_entity = spatial_index
+                .nearest(target_pos, 20.0, SpatialKind::Resource)
+                .map(|(entity, _, _)| entity);
+        }
+        
+        if self.resource_entity.is_none() {
+            println!("🔍 Aucune ressource trouvée à la position cible, réinitialisation");
+            station.known_deposits.retain(|deposit| deposit.position != target_pos);
+            discovered_resource.position = None;
+            self.position = None;
+        }
+    }
+
+    pub(crate) fn deposit_available(&self, deposits_query: &Query<(&Resource, &mut Deposit)>) -> bool {
+        self.resource_entity
+            .and_then(|entity| deposits_query.get(entity).ok())
+            .is_some_and(|(_, deposit)| !deposit.is_exhausted())
+    }
+
+    // Le gisement est épuisé: la station pourra confier le suivant aux mineurs
+    pub(crate) fn release(&mut self, discovered_resource: &mut DiscoveredResource) {
+        self.resource_entity = None;
+        self.position = None;
+        discovered_resource.position = None;
     }
 }
 
-pub struct SimulationPlugin;
+#[derive(Default)]
+pub struct SimulationPlugin {
+    pub scenario: Scenario,
+}
+
+impl SimulationPlugin {
+    pub fn new(scenario: Scenario) -> Self {
+        Self { scenario }
+    }
+}
 
 impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
-        let seed = rand::random::<u32>();
-        let cell_size_astar = 20.0;
-        let game_map = generate_map(800.0, 600.0, cell_size_astar, seed);
-
-        let map_resources = MapResources {
-            energy_positions: vec![
-                Vec2::new(200.0, 150.0),
-                Vec2::new(-200.0, 150.0),
-                Vec2::new(0.0, -150.0),
-                Vec2::new(150.0, 0.0),
-                Vec2::new(-150.0, 0.0),
-                Vec2::new(250.0, -100.0),
-                Vec2::new(-250.0, 100.0),
-            ],
-            mineral_positions: vec![
-                Vec2::new(-200.0, -150.0),
-                Vec2::new(200.0, -150.0),
-                Vec2::new(0.0, 150.0),
-                Vec2::new(100.0, -50.0),
-                Vec2::new(-100.0, 50.0),
-                Vec2::new(150.0, 200.0),
-                Vec2::new(-150.0, -200.0),
-            ],
-            scientific_sites: vec![],
+        if let Err(error) = self.scenario.validate() {
+            panic!("❌ {}: {}", self.scenario.name, error);
+        }
+        
+        let seed = self.scenario.map.seed.unwrap_or_else(rand::random::<u32>);
+        let scenario = self.scenario.clone().with_seed(seed);
+        println!("📜 Scénario: {}", scenario.name);
+        println!("🌱 Seed de la carte: {}", seed);
+        
+        let game_map = scenario.map.generate(seed);
+        let explored_zones = ExploredZones::for_map(&game_map);
+        let map_resources = scenario.resources.place(&scenario.map, &scenario.base_positions(), seed);
+        let behavior_trees = match scenario.behaviors.load() {
+            Ok(trees) => trees,
+            Err(error) => panic!("❌ {}: {}", scenario.name, error),
+        };
+        let robot_scripts = match scenario.scripts.load() {
+            Ok(scripts) => scripts,
+            Err(error) => panic!("❌ {}: {}", scenario.name, error),
         };
 
         app.insert_resource(game_map)
             .insert_resource(map_resources)
             .insert_resource(DiscoveredResource::default())
-            .insert_resource(ExploredZones::default())
+            .insert_resource(explored_zones)
             .insert_resource(CollectionState::default())
-            .insert_resource(ExplorerState {
-                current_direction: Vec2::new(1.0, 0.0),
-                time_until_change: 2.0,
-            })
-            .add_systems(Startup, (setup, debug_draw_map))
+            .insert_resource(SpatialIndex::default())
+            .insert_resource(Station::default())
+            .insert_resource(SimulationStats::default())
+            .insert_resource(MissionStatus::default())
+            .insert_resource(SimulationRng(StdRng::seed_from_u64(seed as u64)))
+            .insert_resource(scenario.exploration_mode)
+            .insert_resource(scenario.timers.clone())
+            .insert_resource(scenario.stop_conditions.clone())
+            .insert_resource(scenario.comms.clone())
+            .insert_resource(scenario.construction.clone())
+            .insert_resource(OutpostOrders::from_config(&scenario.construction))
+            .insert_resource(scenario.production.clone())
+            .insert_resource(ProductionQueue::default())
+            .insert_resource(scenario.reliability.clone())
+            .insert_resource(scenario.environment.clone())
+            .insert_resource(EnvironmentClock::default())
+            .insert_resource(scenario.daylight.clone())
+            .insert_resource(Network::default())
+            .insert_resource(behavior_trees)
+            .insert_resource(robot_scripts)
+            .insert_resource(scenario)
+            .init_resource::<StuckSettings>()
+            .add_event::<StateTransition>()
+            .add_event::<RobotStuck>()
+            .add_event::<MissionEnded>()
+            .add_event::<EnvironmentEvent>()
+            .add_systems(Startup, (setup, spawn_night_overlay))
             .add_systems(
                 Update,
+                (debug_draw_map, invalidate_paths)
+                    .run_if(resource_changed::<GameMap>())
+                    .before(run_simulation_ticks),
+            )
+            .init_resource::<SimulationClock>()
+            .init_resource::<ManualControl>()
+            .add_systems(Update, (apply_manual_control, attach_behavior_trees, attach_robot_scripts).before(run_simulation_ticks))
+            .add_systems(Update, run_simulation_ticks)
+            .add_systems(
+                SimulationTick,
                 (
+                    sync_spatial_index,
+                    update_stats_clock,
+                    (trigger_environment_events, react_to_environment, update_storms).chain(),
                     check_resource_discovery,
-                    move_explorer,
-                    move_miners,
+                    dispatch_miners,
+                    build_outposts,
+                    (plan_production, advance_production).chain(),
+                    (wear_and_failures, repair_robots, update_batteries).chain(),
+                    (move_explorer, move_miners, run_behavior_trees, run_robot_scripts).chain(),
+                    (drive_manual_robots, operate_manual_miners).chain(),
+                    recover_stuck_robots,
+                    separate_robots,
+                    detect_stuck_robots,
                     update_explored_map,
+                    (share_map_patches, announce_task_claims, transmit_messages, deliver_messages).chain(),
+                    update_deposit_sprites,
+                    check_state_invariants,
+                    analyze_scientific_sites,
+                    evaluate_objectives,
                 )
-            );
+                    .chain()
+                    .run_if(mission_running)
+            )
+            .add_systems(Update, (update_night_overlay, print_final_report).after(run_simulation_ticks));
     }
 }
 
 fn setup(
     mut commands: Commands,
+    scenario: Res<Scenario>,
     map_resources: Res<MapResources>,
     mut game_map: ResMut<GameMap>,
 ) {
-    commands.spawn(Camera2dBundle::default());
+    for base in scenario.base_positions() {
+        spawn_base(&mut commands, &mut game_map, &base);
+    }
 
-    commands.spawn((
+    for spec in &scenario.robots {
+        for position in spec.positions() {
+            clear_obstacles_around_position(&mut game_map, &position, 1);
+            spawn_robot(&mut commands, spec, &position, &scenario.timers);
+        }
+    }
+
+    clear_obstacles_around_resources(&mut game_map, &map_resources);
+
+    for (positions, color, resource) in [
+        (&map_resources.energy_positions, Color::YELLOW, Resource::Energy),
+        (&map_resources.mineral_positions, Color::BLUE, Resource::Mineral),
+    ] {
+        for pos in positions {
+            let deposit = spawn_persistent_resource(&mut commands, pos, color, resource);
+            // Un gisement entamé, exporté par l'éditeur, reprend avec sa quantité restante
+            let remaining = scenario.resources.deposit_quantity(*pos, resource);
+            if remaining != resource.initial_quantity() {
+                commands.entity(deposit).insert(Deposit {
+                    remaining,
+                    initial: resource.initial_quantity(),
+                });
+            }
+        }
+    }
+    
+    for pos in &map_resources.scientific_sites {
+        spawn_scientific_site(&mut commands, pos);
+    }
+}
+
+pub fn spawn_robot(commands: &mut Commands, spec: &RobotSpec, position: &Vec2, timers: &Timers) -> Entity {
+    let (color, size) = match spec.kind {
+        RobotKind::Explorer => (Color::GREEN, Vec2::new(15.0, 20.0)),
+        RobotKind::Miner => (Color::rgb(1.0, 0.5, 0.0), Vec2::new(10.0, 10.0)),
+    };
+    let mut robot = commands.spawn((
         SpriteBundle {
             sprite: Sprite {
-                color: Color::BLUE,
-                custom_size: Some(Vec2::new(30.0, 30.0)),
+                color,
+                custom_size: Some(size),
                 ..default()
             },
-            transform: Transform::from_xyz(0., 0., 0.),
+            transform: Transform::from_xyz(position.x, position.y, 0.),
             ..default()
         },
-        Base,
+        RobotBody { radius: spec.radius() },
+        Kinematics::new(spec.motion()),
+        StuckWatchdog::default(),
+        PlannedPath::default(),
+        Radio::default(),
+        Reliability::default(),
+        Battery::default(),
+        SpatialKind::Robot,
     ));
 
+    match spec.kind {
+        RobotKind::Explorer => {
+            robot.insert((
+                Explorer,
+                RobotState::Exploring,
+                ExplorerState {
+                    current_direction: Vec2::new(1.0, 0.0),
+                    time_until_change: timers.explorer_turn_interval,
+                },
+            ));
+        }
+        RobotKind::Miner => {
+            robot.insert((Miner, RobotState::Idle, Cargo::default()));
+        }
+    }
+    if let Some(sensor) = spec.sensor() {
+        robot.insert(sensor);
+    }
+    robot.id()
+}
+
+pub fn spawn_base(commands: &mut Commands, game_map: &mut GameMap, position: &Vec2) {
+    clear_obstacles_around_position(game_map, position, 5);
     commands.spawn((
         SpriteBundle {
             sprite: Sprite {
-                color: Color::GREEN,
-                custom_size: Some(Vec2::new(15.0, 20.0)),
+                color: Color::BLUE,
+                custom_size: Some(Vec2::new(30.0, 30.0)),
                 ..default()
             },
-            transform: Transform::from_xyz(0., 50., 0.),
+            transform: Transform::from_xyz(position.x, position.y, 0.),
             ..default()
         },
-        Explorer,
+        Base,
     ));
+}
 
-    for i in 0..3 {
-        commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::rgb(1.0, 0.5, 0.0),
-                    custom_size: Some(Vec2::new(10.0, 10.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(30. * (i as f32 - 1.), -30., 0.),
-                ..default()
-            },
-            Miner,
-            IdleMiner,
-        ));
-    }
-
-    clear_obstacles_around_resources(&mut game_map, &map_resources);
-
-    for pos in &map_resources.energy_positions {
-        spawn_persistent_resource(&mut commands, pos, Color::YELLOW, Resource::Energy);
-    }
+pub fn clear_obstacles_around_position(game_map: &mut GameMap, pos: &Vec2, clear_radius: i32) {
+    let grid_x = ((pos.x + game_map.size.x/2.0) / game_map.cell_size) as i32;
+    let grid_y = ((pos.y + game_map.size.y/2.0) / game_map.cell_size) as i32;
     
-    for pos in &map_resources.mineral_positions {
-        spawn_persistent_resource(&mut commands, pos, Color::BLUE, Resource::Mineral);
+    for dy in -clear_radius..=clear_radius {
+        for dx in -clear_radius..=clear_radius {
+            let x = grid_x + dx;
+            let y = grid_y + dy;
+            
+            if x >= 0 && x < game_map.obstacles[0].len() as i32 
+               && y >= 0 && y < game_map.obstacles.len() as i32 {
+                game_map.obstacles[y as usize][x as usize] = false;
+            }
+        }
     }
 }
 
 pub fn clear_obstacles_around_resources(game_map: &mut GameMap, map_resources: &MapResources) {
     let clear_radius = 2;
     
-    let clear_around_position = |map: &mut GameMap, pos: &Vec2| {
-        let grid_x = ((pos.x + map.size.x/2.0) / map.cell_size) as i32;
-        let grid_y = ((pos.y + map.size.y/2.0) / map.cell_size) as i32;
-        
-        for dy in -clear_radius..=clear_radius {
-            for dx in -clear_radius..=clear_radius {
-                let x = grid_x + dx;
-                let y = grid_y + dy;
-                
-                if x >= 0 && x < map.obstacles[0].len() as i32 
-                   && y >= 0 && y < map.obstacles.len() as i32 {
-                    map.obstacles[y as usize][x as usize] = false;
-                }
-            }
-        }
-    };
-    
     for pos in &map_resources.energy_positions {
-        clear_around_position(game_map, pos);
+        clear_obstacles_around_position(game_map, pos, clear_radius);
     }
     
     for pos in &map_resources.mineral_positions {
-        clear_around_position(game_map, pos);
+        clear_obstacles_around_position(game_map, pos, clear_radius);
     }
     
     for pos in &map_resources.scientific_sites {
-        clear_around_position(game_map, pos);
+        clear_obstacles_around_position(game_map, pos, clear_radius);
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn move_explorer(
-    mut explorer_query: Query<&mut Transform, With<Explorer>>,
-    time: Res<Time>,
-    mut explorer_state: ResMut<ExplorerState>,
+    mut explorer_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut RobotState,
+            &mut ExplorerState,
+            &mut Kinematics,
+            &mut PlannedPath,
+            &RobotBody,
+            Option<&Radio>,
+            Option<&Reliability>,
+            Option<&Battery>,
+        ),
+        (With<Explorer>, Without<ManuallyControlled>, Without<BehaviorTree>, Without<RobotScript>),
+    >,
+    base_query: Query<&Transform, (With<Base>, Without<Explorer>)>,
+    comms: Res<CommsConfig>,
+    daylight: Res<DaylightConfig>,
+    network: Res<Network>,
+    stats: Res<SimulationStats>,
+    clock: Res<SimulationClock>,
+    timers: Res<Timers>,
+    mut rng: ResMut<SimulationRng>,
+    mut transitions: EventWriter<StateTransition>,
+    exploration_mode: Res<ExplorationMode>,
     discovered_resource: Res<DiscoveredResource>,
     game_map: Res<GameMap>,
-    miners_query: Query<(), (With<Miner>, Without<IdleMiner>)>,
+    miners_query: Query<&RobotState, (With<Miner>, Without<Explorer>)>,
 ) {
-    if discovered_resource.position.is_some() || !miners_query.is_empty() {
-        return;
-    }
+    let paused = *exploration_mode == ExplorationMode::Serialized
+        && (discovered_resource.position.is_some() || miners_query.iter().any(RobotState::is_busy));
 
-    for mut transform in explorer_query.iter_mut() {
-        explorer_state.time_until_change -= time.delta_seconds();
+    let bases: Vec<Vec2> = base_query.iter().map(|transform| transform.translation.truncate()).collect();
 
-        if explorer_state.time_until_change <= 0.0 {
-            explorer_state.current_direction = Vec2::new(
-                rand::thread_rng().gen_range(-1.0..=1.0),
-                rand::thread_rng().gen_range(-1.0..=1.0),
-            )
-            .normalize_or_zero();
-            explorer_state.time_until_change = 2.0;
+    for (entity, mut transform, mut state, mut explorer_state, mut kinematics, mut path, body, radio, reliability, battery) in
+        explorer_query.iter_mut()
+    {
+        if matches!(*state, RobotState::Stuck | RobotState::Repairing { .. }) {
+            continue;
+        }
+        if battery.is_some_and(Battery::is_empty) {
+            kinematics.speed = 0.0;
+            continue;
         }
 
-        let speed = 100.0;
-        let target_pos = transform.translation + Vec3::new(
-            explorer_state.current_direction.x, 
-            explorer_state.current_direction.y, 
-            0.0
-        ) * 50.0;
-        
-        let (new_pos, rotation) = move_entity_avoiding_obstacles(
-            transform.translation, 
-            target_pos, 
-            speed, 
-            time.delta_seconds(), 
-            &game_map
-        );
-        
-        if new_pos == transform.translation {
-            explorer_state.current_direction = Vec2::new(
-                rand::thread_rng().gen_range(-1.0..=1.0),
-                rand::thread_rng().gen_range(-1.0..=1.0),
-            )
-            .normalize_or_zero();
-            explorer_state.time_until_change = 1.0;
-        } else {
-            transform.translation = new_pos;
-            transform.rotation = rotation;
-            
-            let world_bounds_x = game_map.size.x / 2.0 - 10.0;
-            let world_bounds_y = game_map.size.y / 2.0 - 10.0;
-            
-            if transform.translation.x.abs() > world_bounds_x || transform.translation.y.abs() > world_bounds_y {
-                explorer_state.current_direction = -explorer_state.current_direction;
-                explorer_state.time_until_change = 1.0;
+        let position = transform.translation.truncate();
+        let base = nearest_base(position, &bases);
+
+        // La nuit, la base ne recharge pas: l'explorateur y attend le jour plutôt que de repartir à moitié vide
+        if *state == RobotState::Charging {
+            kinematics.speed = 0.0;
+            if !battery.is_some_and(Battery::is_full) {
+                continue;
+            }
+            println!("🔋 Explorateur {:?} rechargé, reprise de l'exploration", entity);
+            set_state(entity, &mut state, RobotState::Idle, &mut transitions);
+        }
+
+        // Une fois en route, l'explorateur rentre jusqu'au bout au lieu d'osciller autour du seuil
+        let low_battery = battery.is_some_and(|battery| {
+            battery.needs_recharge(position.distance(base), &daylight)
+                || (*state == RobotState::Returning && !battery.is_full())
+        });
+        if low_battery && position.distance(base) < CHARGING_DISTANCE {
+            kinematics.speed = 0.0;
+            path.clear();
+            println!("🔌 Explorateur {:?} en charge à la base", entity);
+            set_state(entity, &mut state, RobotState::Charging, &mut transitions);
+            continue;
+        }
+
+        let report_overdue = comms.report_overdue(radio, &network, stats.elapsed);
+        let needs_repair = reliability.is_some_and(|reliability| reliability.repair_requested);
+        if report_overdue || needs_repair || low_battery {
+            if *state != RobotState::Returning {
+                if needs_repair {
+                    println!("🔧 Explorateur {:?} en panne, retour à la base", entity);
+                } else if low_battery {
+                    println!("🪫 Explorateur {:?} bientôt à court d'énergie, retour à la base", entity);
+                } else {
+                    println!("📻 Explorateur {:?} hors de portée, retour à la base pour transmettre", entity);
+                }
             }
+            set_state(entity, &mut state, RobotState::Returning, &mut transitions);
+            drive_towards(&mut transform, &mut kinematics, &mut path, body.radius, base, clock.delta_seconds(), &game_map);
+            continue;
+        }
+        if *state == RobotState::Returning {
+            path.clear();
+            set_state(entity, &mut state, RobotState::Idle, &mut transitions);
+        }
+
+        if paused {
+            kinematics.speed = 0.0;
+            set_state(entity, &mut state, RobotState::Idle, &mut transitions);
+            continue;
         }
+        set_state(entity, &mut state, RobotState::Exploring, &mut transitions);
+        wander(
+            &mut transform,
+            &mut explorer_state,
+            &mut kinematics,
+            body.radius,
+            &mut rng.0,
+            timers.explorer_turn_interval,
+            clock.delta_seconds(),
+            &game_map,
+        );
     }
 }
 
+// Marche aléatoire de l'explorateur: nouvelle direction à intervalle régulier ou devant un obstacle
+#[allow(clippy::too_many_arguments)]
+pub fn wander(
+    transform: &mut Transform,
+    explorer_state: &mut ExplorerState,
+    kinematics: &mut Kinematics,
+    radius: f32,
+    rng: &mut StdRng,
+    turn_interval: f32,
+    delta: f32,
+    game_map: &GameMap,
+) {
+    explorer_state.time_until_change -= delta;
+
+    if explorer_state.time_until_change <= 0.0 {
+        explorer_state.current_direction = Vec2::new(
+            rng.gen_range(-1.0..=1.0),
+            rng.gen_range(-1.0..=1.0),
+        )
+        .normalize_or_zero();
+        explorer_state.time_until_change = turn_interval;
+    }
+
+    let position = transform.translation.truncate();
+    let max_speed = kinematics.config.max_speed;
+    let desired = steering_direction(
+        position, 
+        position + explorer_state.current_direction * 50.0, 
+        max_speed * delta, 
+        radius,
+        game_map
+    );
+    
+    if desired.is_none() {
+        explorer_state.current_direction = Vec2::new(
+            rng.gen_range(-1.0..=1.0),
+            rng.gen_range(-1.0..=1.0),
+        )
+        .normalize_or_zero();
+        explorer_state.time_until_change = turn_interval / 2.0;
+    }
+    
+    drive(transform, kinematics, desired, max_speed, radius, delta, game_map);
+    
+    let world_bounds_x = game_map.size.x / 2.0 - 10.0;
+    let world_bounds_y = game_map.size.y / 2.0 - 10.0;
+    
+    if transform.translation.x.abs() > world_bounds_x || transform.translation.y.abs() > world_bounds_y {
+        explorer_state.current_direction = -explorer_state.current_direction;
+        explorer_state.time_until_change = turn_interval / 2.0;
+    }
+}
+
+// Suit le chemin planifié vers `destination` en freinant à l'approche
+pub fn drive_towards(
+    transform: &mut Transform,
+    kinematics: &mut Kinematics,
+    path: &mut PlannedPath,
+    radius: f32,
+    destination: Vec2,
+    delta: f32,
+    game_map: &GameMap,
+) {
+    let position = transform.translation.truncate();
+    let waypoint = path.next_waypoint(position, destination);
+    let desired = steering_direction(position, waypoint, kinematics.config.max_speed * delta, radius, game_map);
+    let target_speed = kinematics.braking_speed(position.distance(destination));
+    drive(transform, kinematics, desired, target_speed, radius, delta, game_map);
+}
+
 fn update_explored_map(
-    explorer_query: Query<&Transform, With<Explorer>>,
+    sensors_query: Query<(&Transform, &Sensor)>,
     mut explored_zones: ResMut<ExploredZones>,
     game_map: Res<GameMap>,
 ) {
-    if let Ok(explorer_transform) = explorer_query.get_single() {
-        let pos = explorer_transform.translation;
+    for (transform, sensor) in sensors_query.iter() {
+        let origin = transform.translation.truncate();
+        let heading = heading_from_rotation(transform.rotation);
         
-        let grid_x = ((pos.x + game_map.size.x/2.0) / game_map.cell_size) as usize;
-        let grid_y = ((pos.y + game_map.size.y/2.0) / game_map.cell_size) as usize;
-
-        let radius = 2;
-        for dy in -(radius as i32)..=radius as i32 {
-            for dx in -(radius as i32)..=radius as i32 {
-                let x = grid_x as i32 + dx;
-                let y = grid_y as i32 + dy;
-
-                if x >= 0 && x < explored_zones.grid[0].len() as i32 
-                   && y >= 0 && y < explored_zones.grid.len() as i32 {
-                    explored_zones.grid[y as usize][x as usize] = true;
-                }
-            }
+        for cell in sensor.visible_cells(origin, heading, &game_map) {
+            explored_zones.grid[cell.y as usize][cell.x as usize] = true;
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn check_resource_discovery(
-    mut commands: Commands,
-    mut param_set: ParamSet<(
-        Query<&Transform, With<Explorer>>,
-        Query<(Entity, &Transform, &Resource)>
-    )>,
-    mut discovered_resource: ResMut<DiscoveredResource>,
-    idle_miners_query: Query<Entity, With<IdleMiner>>,
-    active_miners: Query<(), (With<Miner>, Without<IdleMiner>)>,
+    mut sensors_query: Query<(Entity, &Transform, &Sensor, Option<&mut Radio>)>,
+    spatial_index: Res<SpatialIndex>,
+    game_map: Res<GameMap>,
+    deposits_query: Query<(&Resource, &Deposit)>,
+    discovered_resource: Res<DiscoveredResource>,
+    exploration_mode: Res<ExplorationMode>,
+    mut station: ResMut<Station>,
+    mut stats: ResMut<SimulationStats>,
+    mut rng: ResMut<SimulationRng>,
+    comms: Res<CommsConfig>,
+    mut network: ResMut<Network>,
+    miners_query: Query<&RobotState, With<Miner>>,
 ) {
-    if discovered_resource.position.is_some() || !active_miners.is_empty() {
-        return;
-    }
-
-    if idle_miners_query.is_empty() {
+    if *exploration_mode == ExplorationMode::Serialized
+        && (discovered_resource.position.is_some() || miners_query.iter().any(RobotState::is_busy))
+    {
         return;
     }
 
-    if let Ok(explorer_transform) = param_set.p0().get_single() {
-        let explorer_pos = explorer_transform.translation;
+    'sensors: for (entity, sensor_transform, sensor, mut radio) in sensors_query.iter_mut() {
+        // Hors partage global, chaque robot ne connaît que ce qu'il a vu ou reçu par radio
+        let mut radio = radio.as_deref_mut().filter(|_| !comms.is_global());
+        let sensor_pos = sensor_transform.translation.truncate();
+        let heading = heading_from_rotation(sensor_transform.rotation);
         
-        let mut closest_resource: Option<(Vec2, f32)> = None;
+        let visible_resources: Vec<(Entity, Vec2)> = spatial_index
+            .query_radius(sensor_pos, sensor.effective_range(), SpatialKind::Resource)
+            .into_iter()
+            .filter(|(deposit, pos)| {
+                let known = match &radio {
+                    Some(radio) => radio.knows_deposit(*deposit),
+                    None => station.knows_deposit(*deposit),
+                };
+                !known && sensor.can_see(sensor_pos, heading, *pos, &game_map)
+            })
+            .collect();
         
-        for (_, res_transform, _) in param_set.p1().iter() {
-            let distance = explorer_pos.distance(res_transform.translation);
-            if distance < 35.0 {
-                if closest_resource.is_none() || distance < closest_resource.unwrap().1 {
-                    closest_resource = Some((
-                        Vec2::new(res_transform.translation.x, res_transform.translation.y), 
-                        distance
-                    ));
+        for (deposit_entity, pos) in visible_resources {
+            let Some(measured) = sensor.detect(pos, &mut rng.0) else {
+                continue;
+            };
+            let Ok((resource, deposit)) = deposits_query.get(deposit_entity) else {
+                continue;
+            };
+            
+            println!("🎯 Ressource détectée à {:?} (distance: {:.1})!", measured, measured.distance(sensor_pos));
+            match radio {
+                Some(ref mut radio) => {
+                    let known = KnownDeposit {
+                        entity: deposit_entity,
+                        position: measured,
+                        resource: *resource,
+                        remaining: deposit.remaining,
+                    };
+                    radio.learn_deposit(&known);
+                    network.send(radio, entity, stats.elapsed, Payload::Discovery(known));
+                }
+                None => {
+                    stats.discoveries += 1;
+                    station.register_deposit(deposit_entity, measured, *resource, deposit.remaining);
                 }
             }
-        }
-        
-        if let Some((pos, dist)) = closest_resource {
-            println!("🎯 Ressource détectée à {:?} (distance: {:.1})!", pos, dist);
-            discovered_resource.position = Some(pos);
             
-            let mut miners_activated = 0;
-            for miner_entity in idle_miners_query.iter() {
-                println!("🚀 Activation du mineur {:?}", miner_entity);
-                commands.entity(miner_entity).remove::<IdleMiner>();
-                miners_activated += 1;
+            if *exploration_mode == ExplorationMode::Serialized {
+                break 'sensors;
             }
-            println!("✅ Activé {} mineurs pour collecter la ressource", miners_activated);
         }
     }
 }
 
+fn dispatch_miners(
+    station: Res<Station>,
+    mut discovered_resource: ResMut<DiscoveredResource>,
+    miners_query: Query<&RobotState, With<Miner>>,
+) {
+    if discovered_resource.position.is_some() || !miners_query.iter().any(|state| *state == RobotState::Idle) {
+        return;
+    }
+    
+    let Some(next_deposit) = station.known_deposits.first() else {
+        return;
+    };
+    
+    println!("📋 Gisement {:?} pris dans la file ({} en attente)", next_deposit.entity, station.known_deposits.len());
+    discovered_resource.position = Some(next_deposit.position);
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn move_miners(
     mut commands: Commands,
-    mut param_set: ParamSet<(
-        Query<(Entity, &mut Transform), (With<Miner>, Without<IdleMiner>, Without<ReturningMiner>)>,
-        Query<(Entity, &Transform, &Resource)>,
-        Query<&Transform, With<Base>>,
-        Query<(Entity, &mut Transform), With<ReturningMiner>>
-    )>,
+    mut miners_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Cargo,
+            &mut RobotState,
+            &mut Kinematics,
+            &mut PlannedPath,
+            &RobotBody,
+            Option<&Battery>,
+        ),
+        (With<Miner>, Without<ManuallyControlled>, Without<BehaviorTree>, Without<RobotScript>),
+    >,
+    base_query: Query<&Transform, (With<Base>, Without<Miner>)>,
+    mut deposits_query: Query<(&Resource, &mut Deposit)>,
     mut discovered_resource: ResMut<DiscoveredResource>,
     mut collection_state: ResMut<CollectionState>,
-    time: Res<Time>,
+    mut station: ResMut<Station>,
+    mut stats: ResMut<SimulationStats>,
+    mut transitions: EventWriter<StateTransition>,
+    spatial_index: Res<SpatialIndex>,
+    clock: Res<SimulationClock>,
+    timers: Res<Timers>,
     game_map: Res<GameMap>,
 ) {
-    let base_pos = if let Ok(base_transform) = param_set.p2().get_single() {
-        base_transform.translation
-    } else {
-        Vec3::ZERO
-    };
+    let bases: Vec<Vec2> = base_query.iter().map(|transform| transform.translation.truncate()).collect();
     
-    let mut miners_reached_base = Vec::new();
+    collection_state.refresh(&mut discovered_resource, &mut station, &spatial_index);
+    let mut deposit_available = collection_state.deposit_available(&deposits_query);
     
-    for (entity, mut transform) in param_set.p3().iter_mut() {
-        let current_pos = transform.translation;
-        
-        if current_pos.distance_squared(base_pos) < 15.0 * 15.0 {
-            println!("🏠 Mineur {:?} est revenu à la base", entity);
-            miners_reached_base.push(entity);
+    for (entity, mut transform, mut cargo, mut state, mut kinematics, mut path, body, battery) in miners_query.iter_mut() {
+        // Un mineur à plat attend que ses panneaux le rechargent, sa tâche reste en cours
+        if battery.is_some_and(Battery::is_empty) {
+            kinematics.speed = 0.0;
             continue;
         }
+        let position = transform.translation.truncate();
+        let situation = MinerSituation {
+            position,
+            base: nearest_base(position, &bases),
+            target: collection_state.position,
+            deposit_available,
+            collection_time: timers.collection_time,
+        };
+        let (next_state, action) = miner_step(*state, &situation, clock.delta_seconds());
         
-        let (new_pos, rotation) = move_entity_avoiding_obstacles(
-            current_pos, 
-            base_pos, 
-            120.0, 
-            time.delta_seconds(), 
-            &game_map
-        );
-        
-        transform.translation = new_pos;
-        transform.rotation = rotation;
-    }
-    
-    for entity in miners_reached_base {
-        println!("🔄 Mineur {:?} est maintenant inactif", entity);
-        commands.entity(entity).remove::<ReturningMiner>().insert(IdleMiner);
-    }
-    
-    if collection_state.collecting {
-        collection_state.timer.tick(time.delta());
+        if !matches!(action, MinerAction::MoveTowards(_)) {
+            kinematics.speed = 0.0;
+        }
         
-        if collection_state.timer.finished() {
-            println!("⏱️ Temps de collecte terminé!");
-            
-            if let Some(entity) = collection_state.resource_entity {
-                println!("🗑️ Suppression de la ressource {:?}", entity);
-                commands.entity(entity).despawn();
+        match action {
+            MinerAction::Wait => {}
+            MinerAction::MoveTowards(destination) => {
+                drive_towards(&mut transform, &mut kinematics, &mut path, body.radius, destination, clock.delta_seconds(), &game_map);
             }
-            
-            collection_state.collecting = false;
-            collection_state.resource_entity = None;
-            collection_state.position = None;
-            discovered_resource.position = None;
-            
-            for (entity, _) in param_set.p0().iter() {
-                println!("🏠 Mineur {:?} retourne à la base", entity);
-                commands.entity(entity).insert(ReturningMiner);
+            MinerAction::Load => {
+                let Some(deposit_entity) = collection_state.resource_entity else {
+                    continue;
+                };
+                let Ok((resource, mut deposit)) = deposits_query.get_mut(deposit_entity) else {
+                    continue;
+                };
+                
+                if load_cargo(&mut commands, entity, &mut cargo, deposit_entity, *resource, &mut deposit, &mut station, &mut stats) {
+                    collection_state.release(&mut discovered_resource);
+                    deposit_available = false;
+                }
             }
-            
-            return;
+            MinerAction::Unload => unload_cargo(entity, &mut cargo, &mut station, &mut stats),
         }
+        
+        if let (RobotState::Idle, RobotState::MovingTo(_)) = (*state, next_state) {
+            println!("🚀 Activation du mineur {:?}", entity);
+        }
+        if let (RobotState::MovingTo(_), RobotState::Collecting { .. }) = (*state, next_state) {
+            println!("⏱️ Début de la collecte pour le mineur {:?}", entity);
+        }
+        
+        set_state(entity, &mut state, next_state, &mut transitions);
     }
-    
-    if discovered_resource.position.is_none() {
-        return;
-    }
+}
 
-    let target_pos = discovered_resource.position.unwrap();
-    let target_vec3 = Vec3::new(target_pos.x, target_pos.y, 0.0);
-    
-    if collection_state.position != Some(target_pos) {
-        collection_state.position = Some(target_pos);
+// Prend une unité du gisement; renvoie vrai si le gisement est épuisé et retiré de la carte
+#[allow(clippy::too_many_arguments)]
+pub fn load_cargo(
+    commands: &mut Commands,
+    entity: Entity,
+    cargo: &mut Cargo,
+    deposit_entity: Entity,
+    resource: Resource,
+    deposit: &mut Deposit,
+    station: &mut Station,
+    stats: &mut SimulationStats,
+) -> bool {
+    let taken = deposit.take(1);
+    if taken > 0 {
+        cargo.resource = Some(resource);
+        cargo.amount += taken;
     }
+    println!("🏠 Mineur {:?} retourne à la base", entity);
+    println!("⛏️ Gisement {:?}: {}/{} restants", deposit_entity, deposit.remaining, deposit.initial);
+    station.update_deposit(deposit_entity, deposit.remaining);
     
-    if !collection_state.collecting && collection_state.resource_entity.is_none() {
-        for (entity, transform, _) in param_set.p1().iter() {
-            if transform.translation.distance_squared(target_vec3) < 20.0 * 20.0 {
-                collection_state.resource_entity = Some(entity);
-                break;
-            }
-        }
+    if !deposit.is_exhausted() {
+        return false;
     }
-    
-    if collection_state.resource_entity.is_none() {
-        println!("🔍 Aucune ressource trouvée à la position cible, réinitialisation");
-        discovered_resource.position = None;
-        collection_state.position = None;
-        
-        for (entity, _) in param_set.p0().iter() {
-            commands.entity(entity).insert(ReturningMiner);
-        }
+    println!("🗑️ Gisement {:?} épuisé", deposit_entity);
+    stats.deposits_exhausted += 1;
+    commands.entity(deposit_entity).despawn();
+    true
+}
+
+pub fn unload_cargo(entity: Entity, cargo: &mut Cargo, station: &mut Station, stats: &mut SimulationStats) {
+    println!("🏠 Mineur {:?} est revenu à la base", entity);
+    if cargo.amount == 0 {
         return;
     }
-    
-    let mut miners_at_resource = 0;
-    let mut total_miners = 0;
-    
-    for (_, mut transform) in param_set.p0().iter_mut() {
-        total_miners += 1;
-        let current_pos = transform.translation;
-        
-        if current_pos.distance_squared(target_vec3) < 15.0 * 15.0 {
-            miners_at_resource += 1;
-            continue;
-        }
-        
-        let (new_pos, rotation) = move_entity_avoiding_obstacles(
-            current_pos, 
-            target_vec3, 
-            120.0, 
-            time.delta_seconds(), 
-            &game_map
-        );
-        
-        transform.translation = new_pos;
-        transform.rotation = rotation;
+    println!("📦 Mineur {:?} dépose {} {:?}", entity, cargo.amount, cargo.resource.unwrap());
+    match cargo.resource {
+        Some(Resource::Energy) => stats.energy_collected += cargo.amount,
+        Some(Resource::Mineral) => stats.minerals_collected += cargo.amount,
+        None => {}
     }
-    
-    if miners_at_resource > 0 && !collection_state.collecting {
-        println!("⏱️ Début de la collecte! {}/{} mineurs sont arrivés", miners_at_resource, total_miners);
-        collection_state.collecting = true;
-        collection_state.timer = Timer::from_seconds(2.0, TimerMode::Once);
+    stats.record_delivery(cargo.amount);
+    station.unload(cargo);
+    println!("🏭 Stock de la station: {} énergie, {} minerais", station.energy, station.minerals);
+}
+
+fn update_deposit_sprites(mut deposits_query: Query<(&Deposit, &mut Sprite), Changed<Deposit>>) {
+    for (deposit, mut sprite) in deposits_query.iter_mut() {
+        let size = 15.0 * (0.4 + 0.6 * deposit.fill_ratio());
+        sprite.custom_size = Some(Vec2::new(size, size));
     }
 }
 
+// Ancienne aide de déplacement, gardée pour les tests d'origine : les systèmes passent par `drive`/`slide_circle`
+#[deprecated(note = "utiliser collision::slide_circle")]
 pub fn move_entity_avoiding_obstacles(
     current_pos: Vec3,
     target_pos: Vec3,
     speed: f32,
     delta_time: f32,
+    radius: f32,
     game_map: &GameMap,
 ) -> (Vec3, Quat) {
-    let direction = (target_pos - current_pos).normalize_or_zero();
+    let direction = (target_pos - current_pos).truncate().normalize_or_zero();
     
-    if direction == Vec3::ZERO {
+    if direction == Vec2::ZERO {
         return (current_pos, Quat::IDENTITY);
     }
     
-    let straight_move = direction * speed * delta_time;
-    let next_pos = current_pos + straight_move;
+    let start = current_pos.truncate();
+    let rotation_towards = |v: Vec2| Quat::from_rotation_z(-v.y.atan2(v.x));
+    
+    match avoidance_move(start, direction, speed * delta_time, radius, game_map) {
+        Some(end) => (end.extend(current_pos.z), rotation_towards(end - start)),
+        None => (current_pos, rotation_towards(direction)),
+    }
+}
+
+// Direction de déplacement qui contourne les obstacles, à donner au contrôleur cinématique
+pub fn steering_direction(current: Vec2, target: Vec2, step: f32, radius: f32, game_map: &GameMap) -> Option<Vec2> {
+    let direction = (target - current).try_normalize()?;
+    avoidance_move(current, direction, step, radius, game_map)
+        .and_then(|end| (end - current).try_normalize())
+}
+
+fn avoidance_move(start: Vec2, direction: Vec2, step: f32, radius: f32, game_map: &GameMap) -> Option<Vec2> {
+    let straight = slide_circle(start, start + direction * step, radius, game_map);
+    let is_clear = |pos: Vec2| !circle_overlaps_obstacles(pos, radius, game_map);
     
-    if !is_position_blocked(next_pos, game_map) {
-        let rotation = Quat::from_rotation_z(-direction.y.atan2(direction.x));
-        return (next_pos, rotation);
+    if (straight - start).dot(direction) >= step * 0.3 && is_clear(straight) {
+        return Some(straight);
     }
     
     let angles = [
@@ -540,23 +991,127 @@ pub fn move_entity_avoiding_obstacles(
     ];
     
     for angle in angles {
-        let test_direction = rotate_vec2(Vec2::new(direction.x, direction.y), angle).extend(0.0);
-        let test_pos = current_pos + test_direction * speed * delta_time;
+        let test_direction = rotate_vec2(direction, angle);
+        let moved = slide_circle(start, start + test_direction * step, radius, game_map);
         
-        if !is_position_blocked(test_pos, game_map) {
-            let rotation = Quat::from_rotation_z(-test_direction.y.atan2(test_direction.x));
-            return (test_pos, rotation);
+        if moved.distance(start) >= step * 0.5 && is_clear(moved) {
+            return Some(moved);
+        }
+    }
+    
+    // Robot déjà en contact avec un obstacle: on garde le glissement direct pour qu'il s'en dégage
+    if !is_clear(start) && straight != start {
+        return Some(straight);
+    }
+    
+    None
+}
+
+pub fn nearest_base(position: Vec2, bases: &[Vec2]) -> Vec2 {
+    bases
+        .iter()
+        .copied()
+        .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+        .unwrap_or(Vec2::ZERO)
+}
+
+pub fn overlap_correction(pos: Vec2, radius: f32, other_pos: Vec2, other_radius: f32) -> Vec2 {
+    let offset = pos - other_pos;
+    let min_distance = radius + other_radius;
+    let distance = offset.length();
+    
+    if distance >= min_distance {
+        return Vec2::ZERO;
+    }
+    
+    let direction = if distance > 0.001 { offset / distance } else { Vec2::X };
+    direction * (min_distance - distance) / 2.0
+}
+
+const SEPARATION_PASSES: usize = 8;
+
+fn separate_robots(
+    spatial_index: Res<SpatialIndex>,
+    game_map: Res<GameMap>,
+    mut robots_query: Query<(Entity, &mut Transform, &RobotBody)>,
+) {
+    let mut robots: Vec<(Entity, Vec2, f32)> = robots_query
+        .iter()
+        .map(|(entity, transform, body)| (entity, transform.translation.truncate(), body.radius))
+        .collect();
+    robots.sort_by_key(|(entity, _, _)| *entity);
+    
+    let slots: HashMap<Entity, usize> = robots
+        .iter()
+        .enumerate()
+        .map(|(slot, (entity, _, _))| (*entity, slot))
+        .collect();
+    let max_radius = robots.iter().map(|(_, _, radius)| *radius).fold(0.0, f32::max);
+    
+    let mut pairs = Vec::new();
+    for (slot, (_, pos, _)) in robots.iter().enumerate() {
+        for (other, _) in spatial_index.query_radius(*pos, 2.0 * max_radius + 40.0, SpatialKind::Robot) {
+            if let Some(&other_slot) = slots.get(&other) {
+                if other_slot > slot {
+                    pairs.push((slot, other_slot));
+                }
+            }
+        }
+    }
+    
+    // Plusieurs passes pour résoudre les files de robots coincés dans un couloir
+    for _ in 0..SEPARATION_PASSES {
+        let mut resolved = true;
+        for &(a, b) in &pairs {
+            let (_, pos_a, radius_a) = robots[a];
+            let (_, pos_b, radius_b) = robots[b];
+            let correction = overlap_correction(pos_a, radius_a, pos_b, radius_b);
+            
+            if correction == Vec2::ZERO {
+                continue;
+            }
+            resolved = false;
+            
+            // Si un robot est contre un mur, l'autre s'écarte de la distance restante
+            let push = correction.normalize();
+            let new_a = slide_circle(pos_a, pos_a + correction, radius_a, &game_map);
+            let remaining = correction.length() * 2.0 - (new_a - pos_a).dot(push);
+            let new_b = slide_circle(pos_b, pos_b - push * remaining, radius_b, &game_map);
+            let missing = remaining - (pos_b - new_b).dot(push);
+            
+            robots[a].1 = if missing > 0.01 {
+                slide_circle(new_a, new_a + push * missing, radius_a, &game_map)
+            } else {
+                new_a
+            };
+            robots[b].1 = new_b;
+        }
+        if resolved {
+            break;
         }
     }
     
-    (current_pos, Quat::from_rotation_z(-direction.y.atan2(direction.x)))
+    for (entity, mut transform, _) in robots_query.iter_mut() {
+        let new_pos = robots[slots[&entity]].1;
+        if transform.translation.truncate() != new_pos {
+            transform.translation.x = new_pos.x;
+            transform.translation.y = new_pos.y;
+        }
+    }
+}
+
+// La carte a changé: les chemins calculés et la surveillance de progression ne sont plus valables
+fn invalidate_paths(mut robots_query: Query<(&Transform, &mut PlannedPath, &mut StuckWatchdog)>) {
+    for (transform, mut path, mut watchdog) in robots_query.iter_mut() {
+        path.clear();
+        watchdog.reset(transform.translation.truncate());
+    }
 }
 
 fn debug_draw_map(
     mut commands: Commands,
     query: Query<Entity, With<DebugGrid>>,
     map: Res<GameMap>,
-    map_resources: Res<MapResources>,
 ) {
     for entity in query.iter() {
         commands.entity(entity).despawn();
@@ -584,17 +1139,9 @@ fn debug_draw_map(
             }
         }
     }
-
-    for pos in &map_resources.energy_positions {
-        spawn_resource(&mut commands, pos, Color::YELLOW, Resource::Energy);
-    }
-
-    for pos in &map_resources.mineral_positions {
-        spawn_resource(&mut commands, pos, Color::BLUE, Resource::Mineral);
-    }
 }
 
-fn spawn_resource(commands: &mut Commands, pos: &Vec2, color: Color, resource_type: Resource) {
+pub fn spawn_persistent_resource(commands: &mut Commands, pos: &Vec2, color: Color, resource_type: Resource) -> Entity {
     commands.spawn((
         SpriteBundle {
             sprite: Sprite {
@@ -606,29 +1153,33 @@ fn spawn_resource(commands: &mut Commands, pos: &Vec2, color: Color, resource_ty
             ..default()
         },
         resource_type,
-        DebugGrid,
-    ));
+        Deposit::new(resource_type.initial_quantity()),
+        SpatialKind::Resource,
+    ))
+    .id()
 }
 
-fn spawn_persistent_resource(commands: &mut Commands, pos: &Vec2, color: Color, resource_type: Resource) {
+pub fn spawn_scientific_site(commands: &mut Commands, pos: &Vec2) {
     commands.spawn((
         SpriteBundle {
             sprite: Sprite {
-                color,
-                custom_size: Some(Vec2::new(10.0, 10.0)),
+                color: Color::FUCHSIA,
+                custom_size: Some(Vec2::new(14.0, 14.0)),
                 ..default()
             },
-            transform: Transform::from_xyz(pos.x, pos.y, 0.0),
+            transform: Transform::from_xyz(pos.x, pos.y, 0.5),
             ..default()
         },
-        resource_type,
+        ScientificSite::default(),
     ));
 }
 
+#[allow(clippy::needless_range_loop)]
 pub fn generate_map(width: f32, height: f32, cell_size: f32, seed: u32) -> GameMap {
     let cols = (width / cell_size) as usize;
     let rows = (height / cell_size) as usize;
     let perlin = Perlin::new(seed);
+    let mut rng = StdRng::seed_from_u64(seed as u64);
     
     let mut obstacles = vec![vec![false; cols]; rows];
     
@@ -640,7 +1191,7 @@ pub fn generate_map(width: f32, height: f32, cell_size: f32, seed: u32) -> GameM
             
             obstacles[y][x] = value > 0.55;
             
-            if !obstacles[y][x] && rand::random::<f32>() < 0.05 {
+            if !obstacles[y][x] && rng.gen::<f32>() < 0.05 {
                 obstacles[y][x] = true;
             }
         }
@@ -648,10 +1199,10 @@ pub fn generate_map(width: f32, height: f32, cell_size: f32, seed: u32) -> GameM
     
     if cols >= 15 && rows >= 15 {
         for _ in 0..5 {
-            let start_x = rand::thread_rng().gen_range(5..cols-5);
-            let start_y = rand::thread_rng().gen_range(5..rows-5);
-            let length = rand::thread_rng().gen_range(3..10);
-            let horizontal = rand::random::<bool>();
+            let start_x = rng.gen_range(5..cols-5);
+            let start_y = rng.gen_range(5..rows-5);
+            let length = rng.gen_range(3..10);
+            let horizontal = rng.gen::<bool>();
             
             for i in 0..length {
                 if horizontal {
@@ -685,6 +1236,8 @@ pub fn generate_map(width: f32, height: f32, cell_size: f32, seed: u32) -> GameM
     }
 }
 
+// Ancien test de blocage ponctuel, remplacé par la collision balayée (`circle_overlaps_obstacles`)
+#[deprecated(note = "utiliser collision::circle_overlaps_obstacles")]
 pub fn is_position_blocked(pos: Vec3, game_map: &GameMap) -> bool {
     let grid_x = ((pos.x + game_map.size.x/2.0) / game_map.cell_size) as i32;
     let grid_y = ((pos.y + game_map.size.y/2.0) / game_map.cell_size) as i32;
@@ -719,6 +1272,54 @@ pub fn is_position_blocked(pos: Vec3, game_map: &GameMap) -> bool {
     false
 }
 
+pub fn cells_along_segment(from: Vec2, to: Vec2, game_map: &GameMap) -> Vec<IVec2> {
+    let start = (from + game_map.size / 2.0) / game_map.cell_size;
+    let end = (to + game_map.size / 2.0) / game_map.cell_size;
+    let delta = end - start;
+    
+    let mut cell = start.floor().as_ivec2();
+    let target = end.floor().as_ivec2();
+    let step = IVec2::new(
+        if delta.x > 0.0 { 1 } else if delta.x < 0.0 { -1 } else { 0 },
+        if delta.y > 0.0 { 1 } else if delta.y < 0.0 { -1 } else { 0 },
+    );
+    
+    let first_boundary = |c: i32, s: i32, origin: f32, d: f32| {
+        if s == 0 {
+            f32::INFINITY
+        } else {
+            ((c + (s > 0) as i32) as f32 - origin) / d
+        }
+    };
+    let mut t_max = Vec2::new(
+        first_boundary(cell.x, step.x, start.x, delta.x),
+        first_boundary(cell.y, step.y, start.y, delta.y),
+    );
+    let t_delta = Vec2::new(
+        if step.x == 0 { f32::INFINITY } else { 1.0 / delta.x.abs() },
+        if step.y == 0 { f32::INFINITY } else { 1.0 / delta.y.abs() },
+    );
+    
+    let mut cells = vec![cell];
+    let max_steps = (target.x - cell.x).abs() + (target.y - cell.y).abs();
+    
+    for _ in 0..max_steps {
+        if cell == target {
+            break;
+        }
+        if t_max.x < t_max.y {
+            cell.x += step.x;
+            t_max.x += t_delta.x;
+        } else {
+            cell.y += step.y;
+            t_max.y += t_delta.y;
+        }
+        cells.push(cell);
+    }
+    
+    cells
+}
+
 pub fn rotate_vec2(v: Vec2, angle_rad: f32) -> Vec2 {
     let (s, c) = angle_rad.sin_cos();
     Vec2::new(v.x * c - v.y * s, v.x * s + v.y * c)
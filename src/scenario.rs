@@ -0,0 +1,632 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+use crate::behavior::BehaviorConfig;
+use crate::comms::CommsConfig;
+use crate::construction::ConstructionConfig;
+use crate::daylight::DaylightConfig;
+use crate::environment::EnvironmentConfig;
+use crate::motion::MotionConfig;
+use crate::production::{ProductionConfig, ProductionPolicy};
+use crate::reliability::ReliabilityConfig;
+use crate::robots::{generate_map, ExplorationMode, GameMap, MapResources, Resource};
+use crate::scripting::ScriptConfig;
+use crate::sensor::Sensor;
+use crate::state::COLLECTION_TIME;
+
+const MAX_PLACEMENT_ATTEMPTS: usize = 100;
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::Io(error) => write!(f, "lecture impossible: {}", error),
+            ScenarioError::Parse(error) => write!(f, "format invalide: {}", error),
+            ScenarioError::Serialize(error) => write!(f, "écriture impossible: {}", error),
+            ScenarioError::Invalid(reason) => write!(f, "scénario invalide: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+// `Grid` décrit la carte case par case, de la rangée du haut à celle du bas ('#' mur, '.' libre)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MapGenerator {
+    Perlin,
+    Empty,
+    Grid(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapConfig {
+    pub width: f32,
+    pub height: f32,
+    #[serde(default = "default_cell_size")]
+    pub cell_size: f32,
+    #[serde(default)]
+    pub seed: Option<u32>,
+    #[serde(default = "default_generator")]
+    pub generator: MapGenerator,
+}
+
+fn default_cell_size() -> f32 {
+    20.0
+}
+
+fn default_generator() -> MapGenerator {
+    MapGenerator::Perlin
+}
+
+impl MapConfig {
+    pub fn generate(&self, seed: u32) -> GameMap {
+        let mut game_map = generate_map(self.width, self.height, self.cell_size, seed);
+        match &self.generator {
+            MapGenerator::Perlin => {}
+            MapGenerator::Empty => {
+                for row in game_map.obstacles.iter_mut() {
+                    row.fill(false);
+                }
+            }
+            MapGenerator::Grid(rows) => {
+                for (y, row) in game_map.obstacles.iter_mut().rev().enumerate() {
+                    for (cell, symbol) in row.iter_mut().zip(rows[y].chars()) {
+                        *cell = symbol == '#';
+                    }
+                }
+            }
+        }
+        game_map
+    }
+
+    pub fn columns(&self) -> usize {
+        (self.width / self.cell_size) as usize
+    }
+
+    pub fn rows(&self) -> usize {
+        (self.height / self.cell_size) as usize
+    }
+
+    pub fn contains(&self, (x, y): (f32, f32)) -> bool {
+        x.abs() <= self.width / 2.0 && y.abs() <= self.height / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseConfig {
+    pub position: (f32, f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RobotKind {
+    Explorer,
+    Miner,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorConfig {
+    pub range: f32,
+    pub field_of_view_degrees: f32,
+    #[serde(default)]
+    pub false_negative_rate: f64,
+    #[serde(default)]
+    pub position_noise: f32,
+}
+
+impl SensorConfig {
+    pub fn to_sensor(&self) -> Sensor {
+        Sensor {
+            range: self.range,
+            field_of_view: self.field_of_view_degrees.to_radians(),
+            false_negative_rate: self.false_negative_rate,
+            position_noise: self.position_noise,
+            ..default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobotSpec {
+    pub kind: RobotKind,
+    pub position: (f32, f32),
+    #[serde(default = "default_count")]
+    pub count: u32,
+    #[serde(default = "default_spacing")]
+    pub spacing: (f32, f32),
+    #[serde(default)]
+    pub radius: Option<f32>,
+    #[serde(default)]
+    pub motion: Option<MotionConfig>,
+    #[serde(default)]
+    pub sensor: Option<SensorConfig>,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_spacing() -> (f32, f32) {
+    (30.0, 0.0)
+}
+
+impl RobotSpec {
+    pub fn radius(&self) -> f32 {
+        self.radius.unwrap_or(match self.kind {
+            RobotKind::Explorer => 10.0,
+            RobotKind::Miner => 6.0,
+        })
+    }
+
+    pub fn motion(&self) -> MotionConfig {
+        self.motion.unwrap_or(match self.kind {
+            RobotKind::Explorer => MotionConfig::EXPLORER,
+            RobotKind::Miner => MotionConfig::MINER,
+        })
+    }
+
+    // Les explorateurs ont un capteur par défaut, les mineurs seulement s'il est déclaré
+    pub fn sensor(&self) -> Option<Sensor> {
+        match (&self.sensor, self.kind) {
+            (Some(config), _) => Some(config.to_sensor()),
+            (None, RobotKind::Explorer) => Some(Sensor::default()),
+            (None, RobotKind::Miner) => None,
+        }
+    }
+
+    // Position de chaque robot du groupe, alignés selon `spacing` et centrés sur `position`
+    pub fn positions(&self) -> Vec<Vec2> {
+        let origin = Vec2::new(self.position.0, self.position.1);
+        let spacing = Vec2::new(self.spacing.0, self.spacing.1);
+        let offset = (self.count as f32 - 1.0) / 2.0;
+        (0..self.count)
+            .map(|i| origin + spacing * (i as f32 - offset))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ResourcePlacement {
+    Fixed {
+        energy: Vec<(f32, f32)>,
+        minerals: Vec<(f32, f32)>,
+        #[serde(default)]
+        scientific_sites: Vec<(f32, f32)>,
+        // Quantité restante des gisements entamés, par position; les autres partent pleins
+        #[serde(default)]
+        remaining: Vec<((f32, f32), u32)>,
+    },
+    Random {
+        energy: usize,
+        minerals: usize,
+        #[serde(default)]
+        scientific_sites: usize,
+        min_distance_from_base: f32,
+    },
+}
+
+impl ResourcePlacement {
+    // Quantité de départ du gisement placé en `position`, entamé ou non
+    pub fn deposit_quantity(&self, position: Vec2, resource: Resource) -> u32 {
+        match self {
+            ResourcePlacement::Fixed { remaining, .. } => remaining
+                .iter()
+                .find(|&&((x, y), _)| Vec2::new(x, y) == position)
+                .map_or(resource.initial_quantity(), |&(_, amount)| amount),
+            ResourcePlacement::Random { .. } => resource.initial_quantity(),
+        }
+    }
+
+    pub fn place(&self, map: &MapConfig, bases: &[Vec2], seed: u32) -> MapResources {
+        let to_vec2 = |points: &[(f32, f32)]| points.iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+
+        match self {
+            ResourcePlacement::Fixed { energy, minerals, scientific_sites, .. } => MapResources {
+                energy_positions: to_vec2(energy),
+                mineral_positions: to_vec2(minerals),
+                scientific_sites: to_vec2(scientific_sites),
+            },
+            ResourcePlacement::Random { energy, minerals, scientific_sites, min_distance_from_base } => {
+                let mut rng = StdRng::seed_from_u64(seed as u64 ^ 0x5eed);
+                let margin = map.cell_size * 3.0;
+                let mut pick = |count: usize| -> Vec<Vec2> {
+                    let mut points = Vec::new();
+                    let mut attempts = 0;
+                    while points.len() < count && attempts < count * MAX_PLACEMENT_ATTEMPTS {
+                        attempts += 1;
+                        let point = Vec2::new(
+                            rng.gen_range(-map.width / 2.0 + margin..map.width / 2.0 - margin),
+                            rng.gen_range(-map.height / 2.0 + margin..map.height / 2.0 - margin),
+                        );
+                        if bases.iter().all(|base| base.distance(point) >= *min_distance_from_base) {
+                            points.push(point);
+                        }
+                    }
+                    if points.len() < count {
+                        println!("⚠️ Seulement {} ressources placées sur {}", points.len(), count);
+                    }
+                    points
+                };
+
+                MapResources {
+                    energy_positions: pick(*energy),
+                    mineral_positions: pick(*minerals),
+                    scientific_sites: pick(*scientific_sites),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct Timers {
+    pub collection_time: f32,
+    pub explorer_turn_interval: f32,
+}
+
+impl Default for Timers {
+    fn default() -> Self {
+        Self {
+            collection_time: COLLECTION_TIME,
+            explorer_turn_interval: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    CollectMinerals(u32),
+    CollectEnergy(u32),
+    ExploreRatio(f32),
+    AnalyzeScientificSites,
+}
+
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StopConditions {
+    #[serde(default)]
+    pub time_limit: Option<f32>,
+    #[serde(default)]
+    pub objectives: Vec<Objective>,
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub map: MapConfig,
+    #[serde(default)]
+    pub exploration_mode: ExplorationMode,
+    pub bases: Vec<BaseConfig>,
+    pub robots: Vec<RobotSpec>,
+    pub resources: ResourcePlacement,
+    #[serde(default)]
+    pub timers: Timers,
+    #[serde(default)]
+    pub stop_conditions: StopConditions,
+    #[serde(default)]
+    pub comms: CommsConfig,
+    #[serde(default)]
+    pub construction: ConstructionConfig,
+    #[serde(default)]
+    pub production: ProductionConfig,
+    #[serde(default)]
+    pub reliability: ReliabilityConfig,
+    #[serde(default)]
+    pub environment: EnvironmentConfig,
+    #[serde(default)]
+    pub daylight: DaylightConfig,
+    #[serde(default)]
+    pub behaviors: BehaviorConfig,
+    #[serde(default)]
+    pub scripts: ScriptConfig,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "Défaut".into(),
+            description: String::new(),
+            map: MapConfig {
+                width: 2400.0,
+                height: 1800.0,
+                cell_size: default_cell_size(),
+                seed: None,
+                generator: MapGenerator::Perlin,
+            },
+            exploration_mode: ExplorationMode::default(),
+            bases: vec![BaseConfig { position: (0.0, 0.0) }],
+            robots: vec![
+                RobotSpec {
+                    kind: RobotKind::Explorer,
+                    position: (0.0, 50.0),
+                    count: 1,
+                    spacing: default_spacing(),
+                    radius: None,
+                    motion: None,
+                    sensor: None,
+                },
+                RobotSpec {
+                    kind: RobotKind::Miner,
+                    position: (0.0, -30.0),
+                    count: 3,
+                    spacing: default_spacing(),
+                    radius: None,
+                    motion: None,
+                    sensor: None,
+                },
+            ],
+            resources: ResourcePlacement::Fixed {
+                energy: vec![
+                    (200.0, 150.0),
+                    (-200.0, 150.0),
+                    (0.0, -150.0),
+                    (150.0, 0.0),
+                    (-150.0, 0.0),
+                    (250.0, -100.0),
+                    (-250.0, 100.0),
+                ],
+                minerals: vec![
+                    (-200.0, -150.0),
+                    (200.0, -150.0),
+                    (0.0, 150.0),
+                    (100.0, -50.0),
+                    (-100.0, 50.0),
+                    (150.0, 200.0),
+                    (-150.0, -200.0),
+                ],
+                scientific_sites: vec![],
+                remaining: vec![],
+            },
+            timers: Timers::default(),
+            stop_conditions: StopConditions::default(),
+            comms: CommsConfig::default(),
+            construction: ConstructionConfig::default(),
+            production: ProductionConfig::default(),
+            reliability: ReliabilityConfig::default(),
+            environment: EnvironmentConfig::default(),
+            daylight: DaylightConfig::default(),
+            behaviors: BehaviorConfig::default(),
+            scripts: ScriptConfig::default(),
+        }
+    }
+}
+
+impl Scenario {
+    pub fn load(path: impl AsRef<Path>) -> Result<Scenario, ScenarioError> {
+        let text = std::fs::read_to_string(path).map_err(ScenarioError::Io)?;
+        Scenario::from_ron(&text)
+    }
+
+    pub fn from_ron(text: &str) -> Result<Scenario, ScenarioError> {
+        let scenario: Scenario = ron::from_str(text).map_err(ScenarioError::Parse)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ScenarioError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map_err(ScenarioError::Serialize)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ScenarioError> {
+        std::fs::write(path, self.to_ron()?).map_err(ScenarioError::Io)
+    }
+
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.map.seed = Some(seed);
+        self
+    }
+
+    pub fn base_positions(&self) -> Vec<Vec2> {
+        self.bases
+            .iter()
+            .map(|base| Vec2::new(base.position.0, base.position.1))
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        let invalid = |reason: String| Err(ScenarioError::Invalid(reason));
+        let map = &self.map;
+
+        if map.cell_size <= 0.0 || map.width < map.cell_size || map.height < map.cell_size {
+            return invalid(format!(
+                "carte {}x{} trop petite pour des cases de {}",
+                map.width, map.height, map.cell_size
+            ));
+        }
+
+        if let MapGenerator::Grid(rows) = &map.generator {
+            let well_formed = rows.len() == map.rows()
+                && rows.iter().all(|row| {
+                    row.chars().count() == map.columns() && row.chars().all(|symbol| symbol == '#' || symbol == '.')
+                });
+            if !well_formed {
+                return invalid(format!("grille attendue de {}x{} cases ('#' ou '.')", map.columns(), map.rows()));
+            }
+        }
+
+        if self.bases.is_empty() {
+            return invalid("au moins une base est nécessaire".into());
+        }
+        for base in &self.bases {
+            if !map.contains(base.position) {
+                return invalid(format!("base {:?} hors de la carte", base.position));
+            }
+        }
+        for outpost in &self.construction.outposts {
+            if !map.contains(outpost.position) {
+                return invalid(format!("avant-poste {:?} hors de la carte", outpost.position));
+            }
+        }
+
+        if self.robots.is_empty() {
+            return invalid("aucun robot déclaré".into());
+        }
+        for robot in &self.robots {
+            if robot.count == 0 {
+                return invalid(format!("groupe de {:?} vide", robot.kind));
+            }
+            if robot.radius() <= 0.0 {
+                return invalid(format!("rayon invalide pour {:?}", robot.kind));
+            }
+            let motion = robot.motion();
+            if motion.max_speed <= 0.0 || motion.acceleration <= 0.0 || motion.max_turn_rate <= 0.0 {
+                return invalid(format!("mouvement invalide pour {:?}: {:?}", robot.kind, motion));
+            }
+            if let Some(sensor) = &robot.sensor {
+                if sensor.range <= 0.0
+                    || !(0.0..=360.0).contains(&sensor.field_of_view_degrees)
+                    || !(0.0..=1.0).contains(&sensor.false_negative_rate)
+                    || sensor.position_noise < 0.0
+                {
+                    return invalid(format!("capteur invalide pour {:?}", robot.kind));
+                }
+            }
+            for position in robot.positions() {
+                if !map.contains((position.x, position.y)) {
+                    return invalid(format!("{:?} placé hors de la carte en {:?}", robot.kind, position));
+                }
+            }
+        }
+
+        match &self.resources {
+            ResourcePlacement::Fixed { energy, minerals, scientific_sites, remaining } => {
+                for &position in energy.iter().chain(minerals).chain(scientific_sites) {
+                    if !map.contains(position) {
+                        return invalid(format!("ressource {:?} hors de la carte", position));
+                    }
+                }
+                for &(position, amount) in remaining {
+                    let initial = if energy.contains(&position) {
+                        Resource::Energy.initial_quantity()
+                    } else if minerals.contains(&position) {
+                        Resource::Mineral.initial_quantity()
+                    } else {
+                        return invalid(format!("quantité restante pour un gisement absent en {:?}", position));
+                    };
+                    if amount == 0 || amount > initial {
+                        return invalid(format!("quantité restante {} hors de [1, {}] en {:?}", amount, initial, position));
+                    }
+                }
+            }
+            ResourcePlacement::Random { min_distance_from_base, .. } => {
+                if *min_distance_from_base < 0.0
+                    || *min_distance_from_base >= map.width.min(map.height) / 2.0 - map.cell_size * 3.0
+                {
+                    return invalid(format!(
+                        "distance minimale à la base {} incompatible avec la carte",
+                        min_distance_from_base
+                    ));
+                }
+            }
+        }
+
+        if self.timers.collection_time <= 0.0 || self.timers.explorer_turn_interval <= 0.0 {
+            return invalid("les durées doivent être positives".into());
+        }
+
+        if self.stop_conditions.time_limit.is_some_and(|limit| limit <= 0.0) {
+            return invalid("la limite de temps doit être positive".into());
+        }
+        let has_sites = match &self.resources {
+            ResourcePlacement::Fixed { scientific_sites, .. } => !scientific_sites.is_empty(),
+            ResourcePlacement::Random { scientific_sites, .. } => *scientific_sites > 0,
+        };
+        for objective in &self.stop_conditions.objectives {
+            match objective {
+                Objective::ExploreRatio(ratio) if !(0.0..=1.0).contains(ratio) => {
+                    return invalid(format!("ratio d'exploration {} hors de [0, 1]", ratio));
+                }
+                // Sans site, l'objectif serait atteint dès le premier pas
+                Objective::AnalyzeScientificSites if !has_sites => {
+                    return invalid("objectif d'analyse sans site scientifique".into());
+                }
+                _ => {}
+            }
+        }
+
+        let production = &self.production;
+        if production.build_time <= 0.0 || production.queue_limit == 0 {
+            return invalid("la production demande une durée positive et une file non vide".into());
+        }
+        if let ProductionPolicy::KeepRatio { explorers: 0, miners: 0 } = production.policy {
+            return invalid("ratio de production 0:0".into());
+        }
+
+        let reliability = &self.reliability;
+        if reliability.wear_per_distance < 0.0
+            || !(0.0..=1.0).contains(&reliability.failure_rate)
+            || !(0.0..=1.0).contains(&reliability.degraded_speed)
+            || reliability.degraded_speed == 0.0
+            || reliability.repair_time <= 0.0
+        {
+            return invalid(format!("fiabilité invalide: {:?}", reliability));
+        }
+
+        let environment = &self.environment;
+        if environment.event_interval < 0.0
+            || environment.storm_radius <= 0.0
+            || environment.storm_duration <= 0.0
+            || !(0.0..=1.0).contains(&environment.storm_sensor_factor)
+            || environment.rockfall_radius < 0
+        {
+            return invalid(format!("événements d'environnement invalides: {:?}", environment));
+        }
+
+        let daylight = &self.daylight;
+        if daylight.day_length < 0.0
+            || !(0.0..=1.0).contains(&daylight.daylight_ratio)
+            || !(0.0..=1.0).contains(&daylight.night_sensor_factor)
+            || !(0.0..=1.0).contains(&daylight.reserve)
+            || daylight.drain_per_distance < 0.0
+            || daylight.panel_charge_rate < 0.0
+            || daylight.base_charge_rate < 0.0
+        {
+            return invalid(format!("cycle jour/nuit invalide: {:?}", daylight));
+        }
+        // Un robot qui s'arrête pour se recharger attendrait indéfiniment une batterie pleine
+        if daylight.drain_per_distance > 0.0 && daylight.panel_charge_rate == 0.0 && daylight.base_charge_rate == 0.0 {
+            return invalid("les batteries se vident mais aucune recharge n'est possible".into());
+        }
+
+        self.behaviors.load()?;
+        self.scripts.load()?;
+        if (self.behaviors.explorer.is_some() && self.scripts.explorer.is_some())
+            || (self.behaviors.miner.is_some() && self.scripts.miner.is_some())
+        {
+            return invalid("un même type de robot ne peut suivre à la fois un arbre et un script".into());
+        }
+
+        let comms = &self.comms;
+        if comms.range <= 0.0
+            || comms.latency < 0.0
+            || !(0.0..=1.0).contains(&comms.loss_rate)
+            || comms.message_ttl <= 0.0
+            || comms.patch_interval <= 0.0
+            || comms.report_timeout <= 0.0
+        {
+            return invalid(format!("communications invalides: {:?}", comms));
+        }
+
+        Ok(())
+    }
+}
+
+// Rangées de la grille au format de MapGenerator::Grid
+pub fn grid_rows(game_map: &GameMap) -> Vec<String> {
+    game_map
+        .obstacles
+        .iter()
+        .rev()
+        .map(|row| row.iter().map(|&wall| if wall { '#' } else { '.' }).collect())
+        .collect()
+}
@@ -0,0 +1,381 @@
+use bevy::prelude::*;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::behavior::{Action, BehaviorAgent, BehaviorWorld, Blackboard, BlackboardValue, Condition, RobotAgent, Status};
+use crate::comms::Radio;
+use crate::daylight::Battery;
+use crate::manual::ManuallyControlled;
+use crate::motion::Kinematics;
+use crate::pathfinding::PlannedPath;
+use crate::reliability::Reliability;
+use crate::robots::{nearest_base, Cargo, Explorer, ExplorerState, GameMap, Miner, Resource, RobotBody};
+use crate::scenario::ScenarioError;
+use crate::state::RobotState;
+
+// Clé du tableau noir où l'action `move_to` d'un script range sa destination
+const TARGET_KEY: &str = "target";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScriptSource {
+    File(String),
+    Inline(String),
+}
+
+impl ScriptSource {
+    fn read(&self) -> Result<String, ScenarioError> {
+        match self {
+            ScriptSource::File(path) => std::fs::read_to_string(path).map_err(ScenarioError::Io),
+            ScriptSource::Inline(text) => Ok(text.clone()),
+        }
+    }
+}
+
+// Script Rhai par type de robot; il remplace la stratégie codée en dur comme un arbre de comportement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptConfig {
+    pub explorer: Option<ScriptSource>,
+    pub miner: Option<ScriptSource>,
+    // Opérations Rhai autorisées par robot et par pas; au-delà le script est interrompu
+    pub max_operations: u64,
+    // Rayon, en cases, de la portion de carte transmise au script
+    pub perception_radius: i32,
+}
+
+impl Default for ScriptConfig {
+    fn default() -> Self {
+        Self {
+            explorer: None,
+            miner: None,
+            max_operations: 10_000,
+            perception_radius: 3,
+        }
+    }
+}
+
+impl ScriptConfig {
+    pub fn load(&self) -> Result<RobotScripts, ScenarioError> {
+        if self.max_operations == 0 || self.perception_radius < 0 {
+            return Err(ScenarioError::Invalid(format!(
+                "scripts: budget de {} opérations, rayon de perception {}",
+                self.max_operations, self.perception_radius
+            )));
+        }
+        let engine = sandboxed_engine(self.max_operations);
+        let compile = |source: &Option<ScriptSource>, name: &str| -> Result<Option<Arc<AST>>, ScenarioError> {
+            source
+                .as_ref()
+                .map(|source| {
+                    let text = source.read()?;
+                    engine
+                        .compile(text)
+                        .map(Arc::new)
+                        .map_err(|error| ScenarioError::Invalid(format!("script {}: {}", name, error)))
+                })
+                .transpose()
+        };
+        Ok(RobotScripts {
+            explorer: compile(&self.explorer, "explorateur")?,
+            miner: compile(&self.miner, "mineur")?,
+            perception_radius: self.perception_radius,
+            engine,
+        })
+    }
+}
+
+// Rhai n'accède ni aux fichiers ni au réseau; on interdit en plus les imports et on borne
+// le calcul, la récursion et la taille des valeurs
+fn sandboxed_engine(max_operations: u64) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .set_max_operations(max_operations)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(4096)
+        .set_max_array_size(4096)
+        .set_max_map_size(1024)
+        .disable_symbol("eval");
+    engine.on_print(|text| println!("📜 {}", text));
+    engine.on_debug(|text, _, _| println!("📜 {}", text));
+    engine
+}
+
+#[derive(Resource)]
+pub struct RobotScripts {
+    pub engine: Engine,
+    pub explorer: Option<Arc<AST>>,
+    pub miner: Option<Arc<AST>>,
+    pub perception_radius: i32,
+}
+
+#[derive(Component)]
+pub struct RobotScript {
+    pub ast: Arc<AST>,
+    // Variable `memory` du script, conservée d'un pas à l'autre
+    pub memory: Map,
+    pub status: Status,
+    pub errors: u32,
+    blackboard: Blackboard,
+}
+
+impl RobotScript {
+    pub fn new(ast: Arc<AST>) -> Self {
+        Self {
+            ast,
+            memory: Map::new(),
+            status: Status::Success,
+            errors: 0,
+            blackboard: Blackboard::default(),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn attach_robot_scripts(
+    mut commands: Commands,
+    scripts: Res<RobotScripts>,
+    robots_query: Query<(Entity, Option<&Explorer>, Option<&Miner>), (With<RobotBody>, Without<RobotScript>)>,
+) {
+    if scripts.explorer.is_none() && scripts.miner.is_none() {
+        return;
+    }
+    for (entity, explorer, miner) in robots_query.iter() {
+        let script = match (explorer, miner) {
+            (Some(_), _) => &scripts.explorer,
+            (_, Some(_)) => &scripts.miner,
+            _ => &None,
+        };
+        if let Some(ast) = script {
+            commands.entity(entity).insert(RobotScript::new(ast.clone()));
+        }
+    }
+}
+
+fn point(position: Vec2, from: Vec2) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("x".into(), (position.x as f64).into());
+    map.insert("y".into(), (position.y as f64).into());
+    map.insert("distance".into(), (position.distance(from) as f64).into());
+    map.into()
+}
+
+fn resource_name(resource: Resource) -> Dynamic {
+    Dynamic::from(match resource {
+        Resource::Energy => "energy".to_string(),
+        Resource::Mineral => "mineral".to_string(),
+    })
+}
+
+// Cases autour du robot, de la rangée du haut à celle du bas comme `MapGenerator::Grid`:
+// '#' pour un mur ou le bord de la carte, '.' pour une case libre
+fn nearby_cells(game_map: &GameMap, position: Vec2, radius: i32) -> Array {
+    let center = game_map.world_to_cell(position);
+    (-radius..=radius)
+        .rev()
+        .map(|dy| {
+            let row: String = (-radius..=radius)
+                .map(|dx| if game_map.is_obstacle_cell(center + IVec2::new(dx, dy)) { '#' } else { '.' })
+                .collect();
+            Dynamic::from(row)
+        })
+        .collect()
+}
+
+// Perception locale transmise au script dans la constante `robot`
+fn perception(agent: &mut RobotAgent, miner: bool, script: &RobotScript, radius: i32) -> Map {
+    let position = agent.position();
+    let world = &*agent.world;
+    let mut robot = Map::new();
+    robot.insert("kind".into(), Dynamic::from(if miner { "miner" } else { "explorer" }.to_string()));
+    robot.insert("x".into(), (position.x as f64).into());
+    robot.insert("y".into(), (position.y as f64).into());
+    robot.insert("heading".into(), (agent.kinematics.heading as f64).into());
+    robot.insert("state".into(), Dynamic::from(agent.state.name().to_string()));
+    robot.insert("time".into(), (world.stats.elapsed as f64).into());
+    robot.insert("battery".into(), (agent.battery.map_or(1.0, |battery| battery.charge) as f64).into());
+    let cargo = agent.cargo.as_deref();
+    robot.insert("cargo".into(), (cargo.map_or(0, |cargo| cargo.amount) as i64).into());
+    robot.insert(
+        "cargo_kind".into(),
+        cargo.and_then(|cargo| cargo.resource).map_or(Dynamic::UNIT, resource_name),
+    );
+    robot.insert("base".into(), point(agent.base, position));
+    let deposit = world
+        .collection_state
+        .position
+        .filter(|_| miner && world.collection_state.deposit_available(&world.deposits_query));
+    robot.insert("deposit".into(), deposit.map_or(Dynamic::UNIT, |target| point(target, position)));
+    // Hors partage global, le robot ne connaît que les gisements vus ou reçus par sa radio
+    let known_deposits = match agent.radio {
+        Some(radio) if !world.comms.is_global() => &radio.known_deposits,
+        _ => &world.station.known_deposits,
+    };
+    let resources: Array = known_deposits
+        .iter()
+        .filter(|deposit| deposit.remaining > 0)
+        .map(|deposit| {
+            let mut known = point(deposit.position, position).cast::<Map>();
+            known.insert("kind".into(), resource_name(deposit.resource));
+            known.insert("remaining".into(), (deposit.remaining as i64).into());
+            Dynamic::from(known)
+        })
+        .collect();
+    robot.insert("resources".into(), resources.into());
+    robot.insert("cells".into(), nearby_cells(&world.game_map, position, radius).into());
+    let status = match script.status {
+        Status::Success => "success",
+        Status::Failure => "failure",
+        Status::Running => "running",
+    };
+    robot.insert("last_status".into(), Dynamic::from(status.to_string()));
+
+    for (name, condition) in [
+        ("at_base", Condition::AtBase),
+        ("battery_low", Condition::BatteryLow),
+        ("battery_empty", Condition::BatteryEmpty),
+        ("repair_requested", Condition::RepairRequested),
+        ("report_overdue", Condition::ReportOverdue),
+        ("exploration_paused", Condition::ExplorationPaused),
+    ] {
+        robot.insert(name.into(), agent.check(&condition, &script.blackboard).into());
+    }
+    robot
+}
+
+// Traduit la valeur rendue par le script en action: un nom ("wander", "collect"...),
+// `#{ action: "move_to", x: .., y: .. }`, ou rien pour attendre
+fn parse_action(value: Dynamic, blackboard: &mut Blackboard) -> Result<Action, String> {
+    if value.is_unit() {
+        return Ok(Action::Wait);
+    }
+    if value.is_map() {
+        let map = value.cast::<Map>();
+        let name = map.get("action").and_then(|name| name.clone().into_string().ok());
+        if name.as_deref() != Some("move_to") {
+            return Err(format!("action inconnue: {:?}", name));
+        }
+        let coordinate = |key: &str| {
+            map.get(key)
+                .and_then(|value| value.as_float().ok().or_else(|| value.as_int().ok().map(|value| value as f64)))
+        };
+        let (Some(x), Some(y)) = (coordinate("x"), coordinate("y")) else {
+            return Err("move_to sans coordonnées x et y".into());
+        };
+        blackboard.set(TARGET_KEY, BlackboardValue::Position(Vec2::new(x as f32, y as f32)));
+        return Ok(Action::MoveTo(TARGET_KEY.into()));
+    }
+    let name = value.into_string().map_err(|kind| format!("action de type {} inattendue", kind))?;
+    Ok(match name.as_str() {
+        "wander" => Action::Wander,
+        "return_to_base" => Action::ReturnToBase,
+        "collect" => Action::Collect,
+        "unload" => Action::Unload,
+        "scan" => Action::Scan,
+        "charge" => Action::Charge,
+        "wait" => Action::Wait,
+        "halt" => Action::Halt,
+        other => return Err(format!("action inconnue: {}", other)),
+    })
+}
+
+// Chaque robot scripté évalue son script une fois par pas; l'action rendue passe par le même
+// agent que les arbres de comportement, donc par le même code de déplacement et de collecte
+#[allow(clippy::type_complexity)]
+pub fn run_robot_scripts(
+    mut robots_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut RobotState,
+        &mut Kinematics,
+        &mut PlannedPath,
+        &RobotBody,
+        Option<&mut RobotScript>,
+        Option<&mut Cargo>,
+        Option<&mut ExplorerState>,
+        Option<&Battery>,
+        Option<&Reliability>,
+        Option<&Radio>,
+        Option<&Miner>,
+        Option<&ManuallyControlled>,
+    )>,
+    scripts: Res<RobotScripts>,
+    mut world: BehaviorWorld,
+) {
+    let bases: Vec<Vec2> = world.bases_query.iter().map(|transform| transform.translation.truncate()).collect();
+    let paused = world.exploration_paused(
+        robots_query
+            .iter()
+            .any(|(_, _, state, .., miner, _)| miner.is_some() && state.is_busy()),
+    );
+
+    for (
+        entity,
+        mut transform,
+        mut state,
+        mut kinematics,
+        mut path,
+        body,
+        script,
+        mut cargo,
+        mut explorer_state,
+        battery,
+        reliability,
+        radio,
+        miner,
+        manual,
+    ) in robots_query.iter_mut()
+    {
+        let Some(mut script) = script else {
+            continue;
+        };
+        if manual.is_some() || matches!(*state, RobotState::Stuck | RobotState::Repairing { .. }) {
+            continue;
+        }
+
+        let base = nearest_base(transform.translation.truncate(), &bases);
+        let mut agent = RobotAgent {
+            entity,
+            transform: &mut transform,
+            state: &mut state,
+            kinematics: &mut kinematics,
+            path: &mut path,
+            radius: body.radius,
+            cargo: cargo.as_deref_mut(),
+            explorer_state: explorer_state.as_deref_mut(),
+            battery,
+            reliability,
+            radio,
+            base,
+            paused,
+            world: &mut world,
+        };
+        let script = &mut *script;
+        let mut scope = Scope::new();
+        scope.push_constant("robot", perception(&mut agent, miner.is_some(), script, scripts.perception_radius));
+        scope.push("memory", std::mem::take(&mut script.memory));
+        let result = scripts.engine.eval_ast_with_scope::<Dynamic>(&mut scope, &script.ast);
+        script.memory = scope.get_value::<Map>("memory").unwrap_or_default();
+
+        script.blackboard.time = agent.world.stats.elapsed;
+        let action = result
+            .map_err(|error| error.to_string())
+            .and_then(|value| parse_action(value, &mut script.blackboard));
+        script.status = match action {
+            Ok(action) => agent.perform(&action, &mut script.blackboard),
+            Err(error) => {
+                // Un script en erreur ou hors budget immobilise le robot pour ce pas
+                script.errors += 1;
+                agent.world.stats.script_errors += 1;
+                if script.errors == 1 {
+                    println!("📜 Script du robot {:?} interrompu: {}", entity, error);
+                }
+                agent.perform(&Action::Halt, &mut script.blackboard);
+                Status::Failure
+            }
+        };
+    }
+}
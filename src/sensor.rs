@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::robots::{cells_along_segment, GameMap};
+
+#[derive(Component, Debug, Clone)]
+pub struct Sensor {
+    pub range: f32,
+    pub field_of_view: f32,
+    pub false_negative_rate: f64,
+    pub position_noise: f32,
+    // Facteur appliqué à la portée par l'environnement (tempête de poussière)
+    pub attenuation: f32,
+}
+
+impl Default for Sensor {
+    fn default() -> Self {
+        Self {
+            range: 80.0,
+            field_of_view: 240f32.to_radians(),
+            false_negative_rate: 0.0,
+            position_noise: 0.0,
+            attenuation: 1.0,
+        }
+    }
+}
+
+impl Sensor {
+    pub fn effective_range(&self) -> f32 {
+        self.range * self.attenuation
+    }
+
+    pub fn in_field_of_view(&self, origin: Vec2, heading: Vec2, target: Vec2) -> bool {
+        let to_target = target - origin;
+        if to_target.length_squared() < 1e-6 || self.field_of_view >= std::f32::consts::TAU {
+            return true;
+        }
+        heading.angle_between(to_target).abs() <= self.field_of_view / 2.0
+    }
+
+    pub fn can_see(&self, origin: Vec2, heading: Vec2, target: Vec2, game_map: &GameMap) -> bool {
+        origin.distance(target) <= self.effective_range()
+            && self.in_field_of_view(origin, heading, target)
+            && has_line_of_sight(origin, target, game_map)
+    }
+
+    pub fn visible_cells(&self, origin: Vec2, heading: Vec2, game_map: &GameMap) -> Vec<IVec2> {
+        let range = self.effective_range();
+        let min = game_map.world_to_cell(origin - Vec2::splat(range));
+        let max = game_map.world_to_cell(origin + Vec2::splat(range));
+
+        let mut cells = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, y);
+                if !game_map.in_bounds(cell) {
+                    continue;
+                }
+                if cell == game_map.world_to_cell(origin)
+                    || self.can_see(origin, heading, game_map.cell_center(cell), game_map)
+                {
+                    cells.push(cell);
+                }
+            }
+        }
+        cells
+    }
+
+    // Mesure bruitée: peut rater la cible ou renvoyer une position approximative
+    pub fn detect(&self, target: Vec2, rng: &mut impl Rng) -> Option<Vec2> {
+        if self.false_negative_rate > 0.0 && rng.gen_bool(self.false_negative_rate.min(1.0)) {
+            return None;
+        }
+
+        if self.position_noise <= 0.0 {
+            return Some(target);
+        }
+
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let distance = self.position_noise * rng.gen::<f32>().sqrt();
+        Some(target + Vec2::from_angle(angle) * distance)
+    }
+}
+
+pub fn heading_from_rotation(rotation: Quat) -> Vec2 {
+    // Les robots sont orientés avec Quat::from_rotation_z(-angle)
+    let (angle, _, _) = rotation.to_euler(EulerRot::ZYX);
+    Vec2::new(angle.cos(), -angle.sin())
+}
+
+pub fn has_line_of_sight(from: Vec2, to: Vec2, game_map: &GameMap) -> bool {
+    let cells = cells_along_segment(from, to, game_map);
+    let last = cells.len().saturating_sub(1);
+
+    cells
+        .iter()
+        .enumerate()
+        .all(|(i, &cell)| i == 0 || i == last || !game_map.is_obstacle_cell(cell))
+}
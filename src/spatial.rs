@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialKind {
+    Resource,
+    Robot,
+}
+
+#[derive(Resource)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<Entity>>,
+    entries: HashMap<Entity, (Vec2, SpatialKind)>,
+    bounds: Option<(IVec2, IVec2)>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(50.0)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    fn cell_of(&self, pos: Vec2) -> IVec2 {
+        (pos / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn position(&self, entity: Entity) -> Option<Vec2> {
+        self.entries.get(&entity).map(|(pos, _)| *pos)
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: Vec2, kind: SpatialKind) {
+        let cell = self.cell_of(pos);
+
+        if let Some((old_pos, _)) = self.entries.insert(entity, (pos, kind)) {
+            let old_cell = self.cell_of(old_pos);
+            if old_cell == cell {
+                return;
+            }
+            self.remove_from_cell(entity, old_cell);
+        }
+
+        self.cells.entry(cell).or_default().push(entity);
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(cell), max.max(cell)),
+            None => (cell, cell),
+        });
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some((pos, _)) = self.entries.remove(&entity) {
+            let cell = self.cell_of(pos);
+            self.remove_from_cell(entity, cell);
+        }
+    }
+
+    fn remove_from_cell(&mut self, entity: Entity, cell: IVec2) {
+        if let Some(entities) = self.cells.get_mut(&cell) {
+            entities.retain(|&e| e != entity);
+            if entities.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    fn for_each_in_cell(&self, cell: IVec2, kind: SpatialKind, mut f: impl FnMut(Entity, Vec2)) {
+        if let Some(entities) = self.cells.get(&cell) {
+            for &entity in entities {
+                let (pos, entity_kind) = self.entries[&entity];
+                if entity_kind == kind {
+                    f(entity, pos);
+                }
+            }
+        }
+    }
+
+    pub fn query_radius(&self, center: Vec2, radius: f32, kind: SpatialKind) -> Vec<(Entity, Vec2)> {
+        let Some((min_bound, max_bound)) = self.bounds else {
+            return Vec::new();
+        };
+        let min = self.cell_of(center - Vec2::splat(radius)).max(min_bound);
+        let max = self.cell_of(center + Vec2::splat(radius)).min(max_bound);
+
+        let mut found = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.for_each_in_cell(IVec2::new(x, y), kind, |entity, pos| {
+                    if pos.distance_squared(center) <= radius * radius {
+                        found.push((entity, pos));
+                    }
+                });
+            }
+        }
+
+        found.sort_by(|a, b| {
+            a.1.distance_squared(center)
+                .total_cmp(&b.1.distance_squared(center))
+                .then(a.0.cmp(&b.0))
+        });
+        found
+    }
+
+    pub fn nearest(&self, center: Vec2, max_distance: f32, kind: SpatialKind) -> Option<(Entity, Vec2, f32)> {
+        let (min_bound, max_bound) = self.bounds?;
+        let center_cell = self.cell_of(center);
+
+        let extent = (min_bound - center_cell).abs().max((max_bound - center_cell).abs());
+        let max_ring = extent.max_element()
+            .min(((max_distance / self.cell_size).ceil() as i32).saturating_add(1));
+
+        let mut best: Option<(Entity, Vec2, f32)> = None;
+
+        for ring in 0..=max_ring {
+            if let Some((_, _, best_distance)) = best {
+                if (ring - 1) as f32 * self.cell_size > best_distance {
+                    break;
+                }
+            }
+
+            for y in -ring..=ring {
+                for x in -ring..=ring {
+                    if x.abs() != ring && y.abs() != ring {
+                        continue;
+                    }
+
+                    self.for_each_in_cell(center_cell + IVec2::new(x, y), kind, |entity, pos| {
+                        let distance = pos.distance(center);
+                        if distance > max_distance {
+                            return;
+                        }
+                        let closer = match best {
+                            None => true,
+                            Some((best_entity, _, best_distance)) => {
+                                distance < best_distance || (distance == best_distance && entity < best_entity)
+                            }
+                        };
+                        if closer {
+                            best = Some((entity, pos, distance));
+                        }
+                    });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn sync_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    changed_query: Query<(Entity, &Transform, &SpatialKind), Or<(Added<SpatialKind>, Changed<Transform>)>>,
+    mut removed: RemovedComponents<SpatialKind>,
+) {
+    for entity in removed.iter() {
+        index.remove(entity);
+    }
+
+    for (entity, transform, kind) in changed_query.iter() {
+        index.insert(entity, transform.translation.truncate(), *kind);
+    }
+}
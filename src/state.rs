@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+
+use crate::robots::{Explorer, Miner};
+use crate::stats::SimulationStats;
+
+pub const ARRIVAL_DISTANCE: f32 = 15.0;
+pub const COLLECTION_TIME: f32 = 2.0;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum RobotState {
+    Idle,
+    Exploring,
+    MovingTo(Vec2),
+    Collecting { progress: f32 },
+    Unloading,
+    Returning,
+    Charging,
+    Stuck,
+    Repairing { progress: f32 },
+}
+
+impl RobotState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RobotState::Idle => "Idle",
+            RobotState::Exploring => "Exploring",
+            RobotState::MovingTo(_) => "MovingTo",
+            RobotState::Collecting { .. } => "Collecting",
+            RobotState::Unloading => "Unloading",
+            RobotState::Returning => "Returning",
+            RobotState::Charging => "Charging",
+            RobotState::Stuck => "Stuck",
+            RobotState::Repairing { .. } => "Repairing",
+        }
+    }
+
+    // Deux états sont dans la même phase si seule la progression de la collecte ou de la réparation change
+    pub fn same_phase(&self, other: &RobotState) -> bool {
+        match (self, other) {
+            (RobotState::Collecting { .. }, RobotState::Collecting { .. }) => true,
+            (RobotState::Repairing { .. }, RobotState::Repairing { .. }) => true,
+            _ => self == other,
+        }
+    }
+
+    // Un mineur occupé empêche l'exploration en mode sérialisé; un robot en réparation ne compte pas
+    pub fn is_busy(&self) -> bool {
+        !matches!(self, RobotState::Idle | RobotState::Repairing { .. })
+    }
+
+    pub fn can_transition_to(&self, next: &RobotState) -> bool {
+        use RobotState::*;
+
+        if self.same_phase(next) {
+            return true;
+        }
+
+        matches!(
+            (self, next),
+            (Idle, Exploring | MovingTo(_) | Returning | Charging | Repairing { .. })
+                | (Exploring, Idle | MovingTo(_) | Returning | Charging | Stuck)
+                | (MovingTo(_), MovingTo(_) | Collecting { .. } | Returning | Idle | Stuck)
+                | (Collecting { .. }, Returning | Idle)
+                | (Returning, Unloading | Idle | Stuck | Charging | Repairing { .. })
+                | (Unloading, Idle | MovingTo(_) | Charging | Exploring | Repairing { .. })
+                | (Charging, Idle | Exploring | MovingTo(_))
+                | (Stuck, Idle | Exploring | MovingTo(_) | Returning)
+                | (Repairing { .. }, Idle)
+        )
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StateTransition {
+    pub entity: Entity,
+    pub from: RobotState,
+    pub to: RobotState,
+}
+
+pub fn set_state(
+    entity: Entity,
+    state: &mut RobotState,
+    next: RobotState,
+    transitions: &mut EventWriter<StateTransition>,
+) {
+    if !state.same_phase(&next) {
+        transitions.send(StateTransition { entity, from: *state, to: next });
+    }
+    *state = next;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MinerAction {
+    Wait,
+    MoveTowards(Vec2),
+    Load,
+    Unload,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MinerSituation {
+    pub position: Vec2,
+    pub base: Vec2,
+    pub target: Option<Vec2>,
+    pub deposit_available: bool,
+    pub collection_time: f32,
+}
+
+impl MinerSituation {
+    fn available_target(&self) -> Option<Vec2> {
+        self.target.filter(|_| self.deposit_available)
+    }
+}
+
+// Logique des mineurs, indépendante de Bevy: état suivant et action à effectuer
+pub fn miner_step(state: RobotState, situation: &MinerSituation, delta: f32) -> (RobotState, MinerAction) {
+    let returning = (RobotState::Returning, MinerAction::MoveTowards(situation.base));
+
+    match state {
+        RobotState::Idle => match situation.available_target() {
+            Some(target) => (RobotState::MovingTo(target), MinerAction::MoveTowards(target)),
+            None => (RobotState::Idle, MinerAction::Wait),
+        },
+        RobotState::MovingTo(target) => match situation.available_target() {
+            None => returning,
+            Some(current) if current != target => (RobotState::MovingTo(current), MinerAction::MoveTowards(current)),
+            Some(_) if situation.position.distance(target) < ARRIVAL_DISTANCE => {
+                (RobotState::Collecting { progress: 0.0 }, MinerAction::Wait)
+            }
+            Some(_) => (RobotState::MovingTo(target), MinerAction::MoveTowards(target)),
+        },
+        RobotState::Collecting { progress } => {
+            if !situation.deposit_available {
+                return returning;
+            }
+            let progress = progress + delta / situation.collection_time;
+            if progress >= 1.0 {
+                (RobotState::Returning, MinerAction::Load)
+            } else {
+                (RobotState::Collecting { progress }, MinerAction::Wait)
+            }
+        }
+        RobotState::Returning => {
+            if situation.position.distance(situation.base) < ARRIVAL_DISTANCE {
+                (RobotState::Unloading, MinerAction::Unload)
+            } else {
+                returning
+            }
+        }
+        RobotState::Unloading => match situation.available_target() {
+            Some(target) => (RobotState::MovingTo(target), MinerAction::MoveTowards(target)),
+            None => (RobotState::Idle, MinerAction::Wait),
+        },
+        other => (other, MinerAction::Wait),
+    }
+}
+
+pub fn check_state_invariants(
+    mut transitions: EventReader<StateTransition>,
+    robots_query: Query<(Entity, &RobotState, Option<&Miner>, Option<&Explorer>)>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    for transition in transitions.iter() {
+        if !transition.from.can_transition_to(&transition.to) {
+            println!(
+                "⚠️ Transition illégale pour {:?}: {} -> {}",
+                transition.entity,
+                transition.from.name(),
+                transition.to.name()
+            );
+            stats.illegal_transitions += 1;
+        }
+    }
+
+    for (entity, state, miner, explorer) in robots_query.iter() {
+        let allowed = match state {
+            RobotState::Exploring => explorer.is_some(),
+            RobotState::Collecting { .. } | RobotState::Unloading => miner.is_some(),
+            _ => true,
+        };
+
+        if !allowed {
+            println!("⚠️ État {} invalide pour le robot {:?}", state.name(), entity);
+            stats.illegal_transitions += 1;
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::clock::SimulationClock;
+
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SimulationStats {
+    pub elapsed: f32,
+    pub discoveries: u32,
+    pub deposits_exhausted: u32,
+    pub energy_collected: u32,
+    pub minerals_collected: u32,
+    pub delivery_times: Vec<f32>,
+    pub illegal_transitions: u32,
+    pub stuck_events: u32,
+    pub recovery_failures: u32,
+    pub sites_analyzed: u32,
+    pub messages_sent: u32,
+    pub messages_delivered: u32,
+    pub messages_lost: u32,
+    pub outposts_built: u32,
+    pub robots_built: u32,
+    pub failures: u32,
+    pub repairs: u32,
+    pub batteries_depleted: u32,
+    pub script_errors: u32,
+}
+
+impl SimulationStats {
+    pub fn units_collected(&self) -> u32 {
+        self.energy_collected + self.minerals_collected
+    }
+
+    pub fn record_delivery(&mut self, amount: u32) {
+        for _ in 0..amount {
+            self.delivery_times.push(self.elapsed);
+        }
+    }
+
+    // Temps nécessaire pour ramener `units` unités à la station
+    pub fn time_to_collect(&self, units: usize) -> Option<f32> {
+        if units == 0 {
+            return Some(0.0);
+        }
+        self.delivery_times.get(units - 1).copied()
+    }
+}
+
+pub fn update_stats_clock(clock: Res<SimulationClock>, mut stats: ResMut<SimulationStats>) {
+    stats.elapsed += clock.delta_seconds();
+}
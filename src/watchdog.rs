@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::clock::SimulationClock;
+use crate::daylight::Battery;
+use crate::manual::ManuallyControlled;
+use crate::motion::{drive, Kinematics};
+use crate::pathfinding::{find_path, PlannedPath};
+use crate::robots::{
+    nearest_base, rotate_vec2, steering_direction, Base, DiscoveredResource, ExplorerState, GameMap,
+    RobotBody, SimulationRng, Station,
+};
+use crate::scenario::Timers;
+use crate::sensor::heading_from_rotation;
+use crate::state::{set_state, RobotState, StateTransition};
+use crate::stats::SimulationStats;
+
+const BACKOFF_SPEED: f32 = 80.0;
+
+#[derive(Resource, Debug, Clone)]
+pub struct StuckSettings {
+    pub timeout: f32,
+    pub min_progress: f32,
+    pub backoff_time: f32,
+    pub max_attempts: u32,
+}
+
+impl Default for StuckSettings {
+    fn default() -> Self {
+        Self {
+            timeout: 4.0,
+            min_progress: 10.0,
+            backoff_time: 2.0,
+            max_attempts: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recovery {
+    pub resume: RobotState,
+    pub goal: Option<Vec2>,
+    pub direction: Vec2,
+    pub remaining: f32,
+}
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct StuckWatchdog {
+    pub goal: Option<Vec2>,
+    pub anchor: Vec2,
+    pub best_distance: f32,
+    pub stalled_for: f32,
+    pub attempts: u32,
+    pub recovery: Option<Recovery>,
+}
+
+impl StuckWatchdog {
+    pub fn reset(&mut self, position: Vec2) {
+        self.goal = None;
+        self.attempts = 0;
+        self.restart(position);
+    }
+
+    fn restart(&mut self, position: Vec2) {
+        self.anchor = position;
+        self.best_distance = self.goal.map_or(f32::INFINITY, |goal| position.distance(goal));
+        self.stalled_for = 0.0;
+    }
+
+    // Renvoie vrai si le robot n'a pas progressé vers son objectif depuis `timeout` secondes
+    pub fn update(&mut self, position: Vec2, goal: Option<Vec2>, delta: f32, settings: &StuckSettings) -> bool {
+        if goal != self.goal {
+            self.goal = goal;
+            self.attempts = 0;
+            self.restart(position);
+            return false;
+        }
+
+        let progressed = match goal {
+            Some(goal) => {
+                let distance = position.distance(goal);
+                if distance < self.best_distance - settings.min_progress {
+                    self.best_distance = distance;
+                    true
+                } else {
+                    false
+                }
+            }
+            None => position.distance(self.anchor) >= settings.min_progress,
+        };
+
+        if progressed {
+            self.anchor = position;
+            self.stalled_for = 0.0;
+        } else {
+            self.stalled_for += delta;
+        }
+
+        self.stalled_for >= settings.timeout
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RobotStuck {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub state: RobotState,
+    pub attempts: u32,
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn detect_stuck_robots(
+    mut robots_query: Query<
+        (Entity, &Transform, &mut RobotState, &mut StuckWatchdog, Option<&Battery>),
+        Without<ManuallyControlled>,
+    >,
+    base_query: Query<&Transform, (With<Base>, Without<StuckWatchdog>)>,
+    settings: Res<StuckSettings>,
+    clock: Res<SimulationClock>,
+    mut rng: ResMut<SimulationRng>,
+    mut station: ResMut<Station>,
+    mut discovered_resource: ResMut<DiscoveredResource>,
+    mut stats: ResMut<SimulationStats>,
+    mut stuck_events: EventWriter<RobotStuck>,
+    mut transitions: EventWriter<StateTransition>,
+) {
+    let bases: Vec<Vec2> = base_query.iter().map(|transform| transform.translation.truncate()).collect();
+
+    for (entity, transform, mut state, mut watchdog, battery) in robots_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let base_pos = nearest_base(position, &bases);
+        // Un robot à plat est immobile sans être bloqué
+        if battery.is_some_and(Battery::is_empty) {
+            watchdog.reset(position);
+            continue;
+        }
+        let goal = match *state {
+            RobotState::MovingTo(target) => Some(target),
+            RobotState::Returning => Some(base_pos),
+            RobotState::Exploring => None,
+            RobotState::Stuck => continue,
+            _ => {
+                watchdog.reset(position);
+                continue;
+            }
+        };
+
+        if !watchdog.update(position, goal, clock.delta_seconds(), &settings) {
+            continue;
+        }
+
+        watchdog.attempts += 1;
+        stats.stuck_events += 1;
+        println!(
+            "🧱 Robot {:?} bloqué en {} à {:?} (tentative {})",
+            entity,
+            state.name(),
+            position,
+            watchdog.attempts
+        );
+        stuck_events.send(RobotStuck {
+            entity,
+            position,
+            state: *state,
+            attempts: watchdog.attempts,
+        });
+
+        let mut resume = *state;
+        if let RobotState::MovingTo(target) = *state {
+            if watchdog.attempts >= settings.max_attempts {
+                println!("📡 Gisement à {:?} signalé inaccessible à la station", target);
+                station.known_deposits.retain(|deposit| deposit.position != target);
+                if discovered_resource.position == Some(target) {
+                    discovered_resource.position = None;
+                }
+                stats.recovery_failures += 1;
+                resume = RobotState::Returning;
+            }
+        }
+
+        let away = match goal {
+            Some(goal) => (position - goal).normalize_or_zero(),
+            None => -heading_from_rotation(transform.rotation),
+        };
+        let direction = rotate_vec2(away, rng.0.gen_range(-1.2..=1.2));
+
+        let resume_goal = match resume {
+            RobotState::MovingTo(target) => Some(target),
+            RobotState::Returning => Some(base_pos),
+            _ => None,
+        };
+
+        watchdog.recovery = Some(Recovery {
+            resume,
+            goal: resume_goal,
+            direction,
+            remaining: settings.backoff_time,
+        });
+        set_state(entity, &mut state, RobotState::Stuck, &mut transitions);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn recover_stuck_robots(
+    mut robots_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut RobotState,
+            &mut StuckWatchdog,
+            &mut Kinematics,
+            &mut PlannedPath,
+            &RobotBody,
+            Option<&mut ExplorerState>,
+        ),
+        Without<ManuallyControlled>,
+    >,
+    timers: Res<Timers>,
+    clock: Res<SimulationClock>,
+    game_map: Res<GameMap>,
+    mut transitions: EventWriter<StateTransition>,
+) {
+    for (entity, mut transform, mut state, mut watchdog, mut kinematics, mut path, body, explorer_state) in robots_query.iter_mut() {
+        if *state != RobotState::Stuck {
+            continue;
+        }
+
+        let Some(mut recovery) = watchdog.recovery else {
+            set_state(entity, &mut state, RobotState::Idle, &mut transitions);
+            continue;
+        };
+
+        let position = transform.translation.truncate();
+        let desired = steering_direction(
+            position,
+            position + recovery.direction * 50.0,
+            BACKOFF_SPEED * clock.delta_seconds(),
+            body.radius,
+            &game_map,
+        );
+        drive(&mut transform, &mut kinematics, desired, BACKOFF_SPEED, body.radius, clock.delta_seconds(), &game_map);
+
+        recovery.remaining -= clock.delta_seconds();
+        if recovery.remaining > 0.0 {
+            watchdog.recovery = Some(recovery);
+            continue;
+        }
+
+        if let Some(mut explorer_state) = explorer_state {
+            explorer_state.current_direction = recovery.direction;
+            explorer_state.time_until_change = timers.explorer_turn_interval;
+        }
+
+        let position = transform.translation.truncate();
+        if let Some(goal) = recovery.goal {
+            match find_path(position, goal, body.radius, &game_map) {
+                Some(waypoints) => {
+                    println!("🗺️ Robot {:?}: nouveau chemin de {} étapes", entity, waypoints.len());
+                    path.set(goal, waypoints);
+                }
+                None => path.clear(),
+            }
+        }
+
+        println!("🔄 Robot {:?} reprend en {}", entity, recovery.resume.name());
+        watchdog.recovery = None;
+        watchdog.restart(position);
+        set_state(entity, &mut state, recovery.resume, &mut transitions);
+    }
+}
@@ -0,0 +1,80 @@
+use simulation_robots::batch::{
+    parameter_grid, parse_seeds, percentile, run_batch, summarize, write_summary_csv, BatchConfig,
+    Distribution,
+};
+use simulation_robots::robots::ExplorationMode;
+use simulation_robots::scenario::{MapConfig, Scenario};
+
+#[test]
+fn test_percentiles() {
+    let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+    assert_eq!(percentile(&sorted, 0.5), 3.0);
+    assert_eq!(percentile(&sorted, 0.0), 1.0);
+    assert_eq!(percentile(&sorted, 1.0), 5.0);
+    assert!((percentile(&sorted, 0.9) - 4.6).abs() < 1e-5);
+
+    let distribution = Distribution::from_values(&[4.0, 1.0, 3.0, 2.0]);
+    assert_eq!(distribution.count, 4);
+    assert_eq!(distribution.mean, 2.5);
+    assert_eq!(distribution.median, 2.5);
+    assert_eq!(Distribution::from_values(&[]).count, 0);
+}
+
+#[test]
+fn test_grid_and_seeds() {
+    let grid = parameter_grid(&[ExplorationMode::Serialized, ExplorationMode::Concurrent], &[2, 3, 4], &[]);
+    assert_eq!(grid.len(), 6);
+    assert!(grid.iter().all(|set| set.explorers.is_none()));
+    assert_eq!(parameter_grid(&[], &[], &[]).len(), 1);
+
+    let scenario = grid[1].apply(&Scenario::default());
+    assert_eq!(scenario.exploration_mode, ExplorationMode::Serialized);
+    assert_eq!(scenario.robots.iter().map(|spec| spec.count).sum::<u32>(), 4);
+
+    assert_eq!(parse_seeds("3..6").unwrap(), vec![3, 4, 5, 6]);
+    assert_eq!(parse_seeds("1, 8,2").unwrap(), vec![1, 8, 2]);
+    assert!(parse_seeds("5..2").is_err());
+    assert!(parse_seeds("a").is_err());
+}
+
+// Le résultat ne dépend pas du nombre de fils
+#[test]
+fn test_batch_is_deterministic_across_threads() {
+    let scenario = Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            ..Scenario::default().map
+        },
+        ..Scenario::default()
+    };
+    let config = BatchConfig {
+        scenario,
+        seeds: vec![2, 3],
+        parameters: parameter_grid(&[ExplorationMode::Serialized, ExplorationMode::Concurrent], &[], &[]),
+        units: 2,
+        max_time: 20.0,
+        threads: 1,
+    };
+
+    let sequential = run_batch(&config);
+    let parallel = run_batch(&BatchConfig { threads: 4, ..config.clone() });
+    assert_eq!(sequential.len(), 4);
+    for (a, b) in sequential.iter().zip(&parallel) {
+        assert_eq!((a.seed, &a.parameters), (b.seed, &b.parameters));
+        assert_eq!(a.units_collected, b.units_collected);
+        assert_eq!(a.coverage, b.coverage);
+    }
+
+    let summaries = summarize(&config.parameters, &sequential);
+    assert_eq!(summaries.len(), 2);
+    assert!(summaries.iter().all(|summary| summary.runs == 2 && summary.coverage.count == 2));
+
+    let mut csv = Vec::new();
+    write_summary_csv(&summaries, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("parameters,runs,successes,collection_time_count,collection_time_mean"));
+    assert!(lines[1].starts_with("Serialized,2,"));
+}
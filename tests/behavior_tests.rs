@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use simulation_robots::behavior::{
+    Action, BehaviorAgent, BehaviorConfig, BehaviorNode, BehaviorTree, Blackboard, Condition, Status, TreeSource,
+};
+use simulation_robots::headless::headless_app;
+use simulation_robots::scenario::{MapConfig, MapGenerator, Scenario, ScenarioError};
+use simulation_robots::stats::SimulationStats;
+
+// Agent factice: les conditions sont fixées à l'avance et les actions renvoient un statut choisi
+#[derive(Default)]
+struct FakeAgent {
+    has_cargo: bool,
+    wait_status: Option<Status>,
+    performed: Vec<Action>,
+}
+
+impl BehaviorAgent for FakeAgent {
+    fn check(&mut self, condition: &Condition, _blackboard: &Blackboard) -> bool {
+        matches!(condition, Condition::HasCargo) && self.has_cargo
+    }
+
+    fn perform(&mut self, action: &Action, _blackboard: &mut Blackboard) -> Status {
+        self.performed.push(action.clone());
+        match action {
+            Action::Wait => self.wait_status.unwrap_or(Status::Success),
+            Action::Halt => Status::Failure,
+            _ => Status::Success,
+        }
+    }
+}
+
+#[test]
+fn test_composite_nodes() {
+    let tree = BehaviorNode::Selector(vec![
+        BehaviorNode::Sequence(vec![
+            BehaviorNode::Condition(Condition::HasCargo),
+            BehaviorNode::Action(Action::Unload),
+        ]),
+        BehaviorNode::Action(Action::Wander),
+    ]);
+    let mut blackboard = Blackboard::default();
+
+    let mut agent = FakeAgent::default();
+    assert_eq!(tree.tick(&mut agent, &mut blackboard), Status::Success);
+    assert_eq!(agent.performed, vec![Action::Wander]);
+
+    let mut agent = FakeAgent { has_cargo: true, ..default() };
+    assert_eq!(tree.tick(&mut agent, &mut blackboard), Status::Success);
+    assert_eq!(agent.performed, vec![Action::Unload]);
+
+    let parallel = BehaviorNode::Parallel {
+        success_threshold: 2,
+        children: vec![
+            BehaviorNode::Action(Action::Wait),
+            BehaviorNode::Action(Action::Scan),
+            BehaviorNode::Action(Action::Halt),
+        ],
+    };
+    let mut agent = FakeAgent { wait_status: Some(Status::Running), ..default() };
+    assert_eq!(parallel.tick(&mut agent, &mut blackboard), Status::Running);
+    agent.wait_status = Some(Status::Failure);
+    assert_eq!(parallel.tick(&mut agent, &mut blackboard), Status::Failure);
+    agent.wait_status = None;
+    assert_eq!(parallel.tick(&mut agent, &mut blackboard), Status::Success);
+
+    let inverted = BehaviorNode::Inverter(Box::new(BehaviorNode::Condition(Condition::HasCargo)));
+    assert_eq!(inverted.tick(&mut FakeAgent::default(), &mut blackboard), Status::Success);
+}
+
+#[test]
+fn test_blackboard_and_cooldown() {
+    let tree = BehaviorNode::Sequence(vec![
+        BehaviorNode::Inverter(Box::new(BehaviorNode::Condition(Condition::Flag("done".into())))),
+        BehaviorNode::Cooldown {
+            key: "scan_time".into(),
+            seconds: 5.0,
+            child: Box::new(BehaviorNode::Action(Action::Scan)),
+        },
+        BehaviorNode::Action(Action::SetFlag("done".into(), true)),
+    ]);
+    let mut agent = FakeAgent::default();
+    let mut blackboard = Blackboard::default();
+
+    assert_eq!(tree.tick(&mut agent, &mut blackboard), Status::Success);
+    assert!(blackboard.flag("done"));
+    assert_eq!(blackboard.number("scan_time"), Some(0.0));
+    assert_eq!(tree.tick(&mut agent, &mut blackboard), Status::Failure);
+
+    let forget = BehaviorNode::Action(Action::Forget("done".into()));
+    forget.tick(&mut agent, &mut blackboard);
+    assert!(!blackboard.contains("done"));
+    blackboard.time = 2.0;
+    assert_eq!(tree.tick(&mut agent, &mut blackboard), Status::Failure);
+    blackboard.time = 6.0;
+    assert_eq!(tree.tick(&mut agent, &mut blackboard), Status::Success);
+    assert_eq!(agent.performed, vec![Action::Scan, Action::Scan]);
+}
+
+// Les fichiers livrés décrivent exactement les stratégies par défaut
+#[test]
+fn test_bundled_trees_match_defaults() {
+    assert_eq!(BehaviorNode::load("behaviors/explorer.ron").unwrap(), BehaviorNode::explorer());
+    assert_eq!(BehaviorNode::load("behaviors/miner.ron").unwrap(), BehaviorNode::miner());
+
+    let text = BehaviorNode::miner().to_ron().unwrap();
+    assert_eq!(BehaviorNode::from_ron(&text).unwrap(), BehaviorNode::miner());
+}
+
+#[test]
+fn test_invalid_trees_rejected() {
+    let error = BehaviorNode::from_ron("Parallel(success_threshold: 3, children: [Action(Wait)])");
+    assert!(matches!(error, Err(ScenarioError::Invalid(_))));
+    assert!(matches!(BehaviorNode::from_ron("Selector([])"), Err(ScenarioError::Invalid(_))));
+    assert!(matches!(BehaviorNode::from_ron("Action(Fly)"), Err(ScenarioError::Parse(_))));
+
+    let scenario = Scenario {
+        behaviors: BehaviorConfig {
+            miner: Some(TreeSource::File("behaviors/absent.ron".into())),
+            ..default()
+        },
+        ..Scenario::default()
+    };
+    assert!(matches!(scenario.validate(), Err(ScenarioError::Io(_))));
+}
+
+// Pilotés par les arbres par défaut, les robots explorent et ramènent des ressources sans transition illégale
+#[test]
+fn test_default_trees_drive_robots() {
+    let mut app = headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(3),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        behaviors: BehaviorConfig {
+            explorer: Some(TreeSource::Default),
+            miner: Some(TreeSource::Default),
+        },
+        ..Scenario::default()
+    });
+    while app.world.resource::<SimulationStats>().elapsed < 90.0 {
+        app.update();
+    }
+
+    assert_eq!(app.world.query::<&BehaviorTree>().iter(&app.world).count(), 4);
+    let stats = app.world.resource::<SimulationStats>();
+    assert!(stats.units_collected() > 0);
+    assert_eq!(stats.illegal_transitions, 0);
+}
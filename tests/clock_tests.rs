@@ -0,0 +1,71 @@
+use simulation_robots::clock::{SimulationClock, MAX_TIME_SCALE, MIN_TIME_SCALE};
+use simulation_robots::headless::{headless_app, HEADLESS_TIME_STEP};
+use simulation_robots::scenario::{MapConfig, Scenario};
+use simulation_robots::stats::SimulationStats;
+
+#[test]
+fn test_clock_ticks_pause_and_step() {
+    let mut clock = SimulationClock::with_tick(0.1);
+    assert_eq!(clock.ticks_for_frame(0.25), 2);
+    assert_eq!(clock.ticks_for_frame(0.05), 1);
+
+    clock.set_time_scale(4.0);
+    assert_eq!(clock.ticks_for_frame(0.1), 4);
+
+    clock.set_time_scale(100.0);
+    assert_eq!(clock.time_scale, MAX_TIME_SCALE);
+    clock.set_time_scale(0.01);
+    assert_eq!(clock.time_scale, MIN_TIME_SCALE);
+
+    clock.toggle_pause();
+    assert_eq!(clock.ticks_for_frame(10.0), 0);
+    clock.request_step();
+    assert_eq!(clock.ticks_for_frame(0.0), 1);
+    assert_eq!(clock.ticks_for_frame(0.0), 0);
+
+    clock.toggle_pause();
+    clock.request_step();
+    assert_eq!(clock.ticks_for_frame(0.0), 0);
+}
+
+// Tous les systèmes suivent l'horloge: en pause rien ne bouge, en x4 le temps simulé va 4 fois plus vite
+#[test]
+fn test_simulation_follows_clock() {
+    let mut app = headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(5),
+            ..Scenario::default().map
+        },
+        ..Scenario::default()
+    });
+    for _ in 0..10 {
+        app.update();
+    }
+    let elapsed = app.world.resource::<SimulationStats>().elapsed;
+
+    app.world.resource_mut::<SimulationClock>().toggle_pause();
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<SimulationStats>().elapsed, elapsed);
+
+    app.world.resource_mut::<SimulationClock>().request_step();
+    app.update();
+    let stepped = app.world.resource::<SimulationStats>().elapsed;
+    assert!((stepped - elapsed - HEADLESS_TIME_STEP).abs() < 1e-5);
+
+    let ticks = app.world.resource::<SimulationClock>().ticks;
+    {
+        let mut clock = app.world.resource_mut::<SimulationClock>();
+        clock.toggle_pause();
+        clock.set_time_scale(4.0);
+    }
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<SimulationClock>().ticks, ticks + 40);
+    let elapsed = app.world.resource::<SimulationStats>().elapsed;
+    assert!((elapsed - stepped - 40.0 * HEADLESS_TIME_STEP).abs() < 1e-3);
+}
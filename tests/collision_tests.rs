@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use simulation_robots::collision::{circle_overlaps_obstacles, slide_circle};
+use simulation_robots::robots::{move_entity_avoiding_obstacles, GameMap};
+
+fn empty_map() -> GameMap {
+    GameMap {
+        size: Vec2::new(200.0, 200.0),
+        cell_size: 20.0,
+        obstacles: vec![vec![false; 10]; 10],
+        seed: 0,
+    }
+}
+
+// Mur vertical d'une case d'épaisseur en x = [0, 20]
+fn map_with_thin_wall() -> GameMap {
+    let mut game_map = empty_map();
+    for row in game_map.obstacles.iter_mut() {
+        row[5] = true;
+    }
+    game_map
+}
+
+// Un grand pas de temps ne doit pas faire traverser un mur fin
+#[test]
+fn test_no_tunneling_with_high_delta() {
+    let game_map = map_with_thin_wall();
+    let start = Vec3::new(-30.0, 5.0, 0.0);
+    let target = Vec3::new(60.0, 5.0, 0.0);
+
+    for delta in [0.1, 0.5, 1.0, 2.0, 5.0] {
+        let (new_pos, _) = move_entity_avoiding_obstacles(start, target, 120.0, delta, 6.0, &game_map);
+        assert!(new_pos.x <= -6.0 + 0.01, "delta {}: traversée du mur en {:?}", delta, new_pos);
+        assert!(!circle_overlaps_obstacles(new_pos.truncate(), 6.0, &game_map));
+    }
+
+    let reached = slide_circle(Vec2::new(-30.0, 5.0), Vec2::new(60.0, 5.0), 6.0, &game_map);
+    assert!((reached.x + 6.0).abs() < 0.1);
+}
+
+// Un déplacement en biais contre un mur glisse le long de celui-ci
+#[test]
+fn test_slide_along_wall() {
+    let game_map = map_with_thin_wall();
+    let reached = slide_circle(Vec2::new(-20.0, 0.0), Vec2::new(0.0, 20.0), 6.0, &game_map);
+
+    assert!((reached.x + 6.0).abs() < 0.1);
+    assert!((reached.y - 20.0).abs() < 0.1);
+}
+
+// Deux cases qui se touchent par un coin ne laissent aucun passage en diagonale
+#[test]
+fn test_diagonal_corner_is_closed() {
+    let mut game_map = empty_map();
+    game_map.obstacles[5][5] = true;
+    game_map.obstacles[4][4] = true;
+
+    // Les cases (4,4) et (5,5) se touchent en (0,0)
+    let start = Vec2::new(-20.0, 20.0);
+    let end = Vec2::new(20.0, -20.0);
+    let reached = slide_circle(start, end, 3.0, &game_map);
+
+    assert!(reached.y - reached.x > 0.0, "passage par le coin: {:?}", reached);
+    assert!(!circle_overlaps_obstacles(reached, 3.0, &game_map));
+
+    // En arrivant sur un coin saillant, le cercle le contourne au lieu de s'y accrocher
+    let mut game_map = empty_map();
+    game_map.obstacles[5][5] = true;
+    let reached = slide_circle(Vec2::new(-30.0, -29.0), Vec2::new(30.0, 31.0), 6.0, &game_map);
+    assert!(!circle_overlaps_obstacles(reached, 6.0, &game_map));
+    assert!(reached.distance(Vec2::new(-30.0, -29.0)) > 20.0);
+}
+
+// Aucun déplacement aléatoire ne finit dans un obstacle ni hors de la carte
+#[test]
+fn test_random_moves_never_overlap() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut game_map = empty_map();
+    for _ in 0..25 {
+        let (x, y) = (rng.gen_range(0..10), rng.gen_range(0..10));
+        game_map.obstacles[y][x] = true;
+    }
+    game_map.obstacles[0][0] = false;
+
+    let radius = 6.0;
+    let mut pos = game_map.cell_center(IVec2::ZERO);
+
+    for _ in 0..2000 {
+        let motion = Vec2::new(rng.gen_range(-150.0..150.0), rng.gen_range(-150.0..150.0));
+        pos = slide_circle(pos, pos + motion, radius, &game_map);
+        assert!(!circle_overlaps_obstacles(pos, radius, &game_map), "chevauchement en {:?}", pos);
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use simulation_robots::comms::{CommsConfig, CommsModel, Network, Radio};
+use simulation_robots::headless::headless_app;
+use simulation_robots::robots::{GameMap, Station};
+use simulation_robots::scenario::{MapConfig, MapGenerator, ResourcePlacement, RobotKind, RobotSpec, Scenario};
+use simulation_robots::stats::SimulationStats;
+
+fn open_map() -> GameMap {
+    GameMap {
+        size: Vec2::new(400.0, 400.0),
+        cell_size: 20.0,
+        obstacles: vec![vec![false; 20]; 20],
+        seed: 0,
+    }
+}
+
+fn radio_scenario(comms: CommsConfig) -> Scenario {
+    Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(3),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        resources: ResourcePlacement::Fixed {
+            energy: vec![],
+            minerals: vec![(300.0, 200.0), (-300.0, -200.0)],
+            scientific_sites: vec![],
+            remaining: vec![],
+        },
+        comms,
+        ..Scenario::default()
+    }
+}
+
+fn run(app: &mut App, seconds: f32) {
+    while app.world.resource::<SimulationStats>().elapsed < seconds {
+        app.update();
+    }
+}
+
+#[test]
+fn test_links_follow_model_range_and_walls() {
+    let mut game_map = open_map();
+    let mesh = CommsConfig {
+        model: CommsModel::Mesh,
+        range: 100.0,
+        ..default()
+    };
+    let a = Vec2::new(-50.0, 0.0);
+    let b = Vec2::new(40.0, 0.0);
+    assert!(mesh.link(a, b, false, &game_map));
+    assert!(!mesh.link(a, Vec2::new(80.0, 0.0), false, &game_map));
+
+    let station_only = CommsConfig {
+        model: CommsModel::StationOnly,
+        ..mesh.clone()
+    };
+    assert!(!station_only.link(a, b, false, &game_map));
+    assert!(station_only.link(a, b, true, &game_map));
+
+    for row in game_map.obstacles.iter_mut() {
+        row[10] = true;
+    }
+    assert!(!mesh.link(a, b, false, &game_map));
+    let through_walls = CommsConfig {
+        line_of_sight: false,
+        ..mesh
+    };
+    assert!(through_walls.link(a, b, false, &game_map));
+}
+
+// Le modèle global garde le partage instantané, sans aucun message radio
+#[test]
+fn test_global_model_sends_no_messages() {
+    let mut app = headless_app(radio_scenario(CommsConfig::default()));
+    run(&mut app, 20.0);
+
+    let stats = app.world.resource::<SimulationStats>();
+    assert_eq!(stats.messages_sent, 0);
+    assert!(!app.world.resource::<Station>().mapped_cells.is_empty());
+}
+
+// En maillage, les découvertes et la carte finissent par atteindre la station
+#[test]
+fn test_mesh_delivers_discoveries_to_station() {
+    let mut app = headless_app(radio_scenario(CommsConfig {
+        model: CommsModel::Mesh,
+        latency: 1.0,
+        ..default()
+    }));
+    run(&mut app, 120.0);
+
+    let stats = app.world.resource::<SimulationStats>().clone();
+    assert!(stats.discoveries > 0);
+    assert!(stats.messages_delivered > 0);
+    assert_eq!(stats.messages_lost, 0);
+    let station = app.world.resource::<Station>();
+    assert!(!station.mapped_cells.is_empty());
+    assert!(!station.known_deposits.is_empty() || stats.units_collected() > 0);
+}
+
+// Deux explorateurs qui voient le même gisement ne comptent qu'une découverte, reçue par la station
+#[test]
+fn test_shared_sighting_counts_one_discovery() {
+    let mut scenario = radio_scenario(CommsConfig {
+        model: CommsModel::Mesh,
+        ..default()
+    });
+    scenario.robots = vec![RobotSpec {
+        kind: RobotKind::Explorer,
+        position: (0.0, 50.0),
+        count: 2,
+        spacing: (20.0, 0.0),
+        radius: None,
+        motion: None,
+        sensor: None,
+    }];
+    scenario.resources = ResourcePlacement::Fixed {
+        energy: vec![],
+        minerals: vec![(10.0, 100.0)],
+        scientific_sites: vec![],
+        remaining: vec![],
+    };
+    let mut app = headless_app(scenario);
+    run(&mut app, 10.0);
+
+    assert_eq!(app.world.resource::<SimulationStats>().discoveries, 1);
+    assert_eq!(app.world.resource::<Station>().known_deposits.len(), 1);
+}
+
+// Avec une perte totale, la station n'apprend rien et les mineurs restent à quai
+#[test]
+fn test_total_loss_keeps_station_blind() {
+    let mut app = headless_app(radio_scenario(CommsConfig {
+        model: CommsModel::StationOnly,
+        loss_rate: 1.0,
+        ..default()
+    }));
+    run(&mut app, 60.0);
+
+    let stats = app.world.resource::<SimulationStats>();
+    assert!(stats.messages_sent > 0);
+    assert_eq!(stats.messages_lost, stats.messages_sent);
+    assert_eq!(stats.messages_delivered, 0);
+    assert_eq!(stats.discoveries, 0);
+    assert_eq!(stats.units_collected(), 0);
+    let station = app.world.resource::<Station>();
+    assert!(station.known_deposits.is_empty());
+    assert!(station.mapped_cells.is_empty());
+}
+
+// Les identifiants de messages expirés sont oubliés: la mémoire radio ne grandit pas avec la mission
+#[test]
+fn test_expired_messages_are_forgotten() {
+    let mut app = headless_app(radio_scenario(CommsConfig {
+        model: CommsModel::Mesh,
+        message_ttl: 5.0,
+        ..default()
+    }));
+    run(&mut app, 60.0);
+
+    let now = app.world.resource::<SimulationStats>().elapsed;
+    assert!(app.world.resource::<SimulationStats>().messages_delivered > 0);
+    let network = app.world.resource::<Network>();
+    assert!(network.station_seen.values().all(|created_at| now - created_at <= 5.0));
+    let radios: Vec<usize> = app
+        .world
+        .query::<&Radio>()
+        .iter(&app.world)
+        .map(|radio| {
+            assert!(radio.seen.values().all(|created_at| now - created_at <= 5.0));
+            radio.seen.len()
+        })
+        .collect();
+    assert!(radios.iter().any(|&count| count > 0));
+    assert!(app.world.resource::<SimulationStats>().messages_sent > radios.iter().sum::<usize>() as u32);
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use simulation_robots::construction::{pay_outpost, ConstructionConfig, OutpostOrders};
+use simulation_robots::headless::headless_app;
+use simulation_robots::robots::{Base, Station};
+use simulation_robots::scenario::{BaseConfig, MapConfig, MapGenerator, Scenario, ScenarioError};
+use simulation_robots::stats::SimulationStats;
+
+fn outpost_scenario(cost: u32) -> Scenario {
+    Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(4),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        construction: ConstructionConfig {
+            outpost_cost: cost,
+            outposts: vec![BaseConfig { position: (250.0, 150.0) }],
+        },
+        ..Scenario::default()
+    }
+}
+
+#[test]
+fn test_outpost_payment() {
+    let mut station = Station {
+        minerals: 10,
+        ..default()
+    };
+    assert_eq!(pay_outpost(&mut station, 15), None);
+    assert_eq!(station.minerals, 10);
+    assert_eq!(pay_outpost(&mut station, 4), Some(6));
+    assert_eq!(station.minerals, 6);
+
+    let orders = OutpostOrders::from_config(&outpost_scenario(3).construction);
+    assert_eq!(orders.pending.front(), Some(&Vec2::new(250.0, 150.0)));
+}
+
+#[test]
+fn test_outpost_outside_map_rejected() {
+    let mut scenario = outpost_scenario(3);
+    scenario.construction.outposts.push(BaseConfig { position: (900.0, 0.0) });
+    assert!(matches!(scenario.validate(), Err(ScenarioError::Invalid(_))));
+}
+
+// La station construit l'avant-poste dès qu'elle a assez de minerais et le paie sur son stock
+#[test]
+fn test_station_builds_outpost_from_minerals() {
+    let mut app = headless_app(outpost_scenario(3));
+    app.update();
+    assert_eq!(app.world.query_filtered::<(), With<Base>>().iter(&app.world).count(), 1);
+
+    while app.world.resource::<SimulationStats>().outposts_built == 0 {
+        assert!(app.world.resource::<SimulationStats>().elapsed < 300.0, "avant-poste jamais construit");
+        app.update();
+    }
+    app.update();
+
+    let stats = app.world.resource::<SimulationStats>().clone();
+    let station = app.world.resource::<Station>();
+    assert_eq!(station.minerals + 3, stats.minerals_collected);
+    assert!(app.world.resource::<OutpostOrders>().pending.is_empty());
+    let bases: Vec<Vec2> = app
+        .world
+        .query_filtered::<&Transform, With<Base>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    assert_eq!(bases.len(), 2);
+    assert!(bases.contains(&Vec2::new(250.0, 150.0)));
+}
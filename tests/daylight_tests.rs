@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use simulation_robots::daylight::{Battery, DaylightConfig};
+use simulation_robots::headless::headless_app;
+use simulation_robots::robots::Explorer;
+use simulation_robots::scenario::{MapConfig, MapGenerator, Scenario, ScenarioError};
+use simulation_robots::sensor::Sensor;
+use simulation_robots::state::{RobotState, StateTransition};
+use simulation_robots::stats::SimulationStats;
+
+#[derive(Resource, Default)]
+struct SeenTransitions(Vec<StateTransition>);
+
+fn record_transitions(mut transitions: EventReader<StateTransition>, mut seen: ResMut<SeenTransitions>) {
+    seen.0.extend(transitions.iter().copied());
+}
+
+fn daylight_app(daylight: DaylightConfig) -> App {
+    let mut app = headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(12),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        daylight,
+        ..Scenario::default()
+    });
+    app.init_resource::<SeenTransitions>().add_systems(Update, record_transitions);
+    app
+}
+
+fn run(app: &mut App, seconds: f32) {
+    while app.world.resource::<SimulationStats>().elapsed < seconds {
+        app.update();
+    }
+}
+
+#[test]
+fn test_light_level_and_battery() {
+    let config = DaylightConfig {
+        day_length: 100.0,
+        daylight_ratio: 0.5,
+        night_sensor_factor: 0.4,
+        drain_per_distance: 0.01,
+        reserve: 0.1,
+        ..default()
+    };
+    assert_eq!(config.light_level(10.0), 1.0);
+    assert_eq!(config.light_level(75.0), 0.0);
+    assert!((config.light_level(52.5) - 0.5).abs() < 1e-3);
+    assert!(config.is_daylight(110.0));
+    assert!(!config.is_daylight(80.0));
+    assert!((config.sensor_factor(75.0) - 0.4).abs() < 1e-6);
+    assert_eq!(DaylightConfig::default().light_level(1234.0), 1.0);
+
+    let mut battery = Battery::default();
+    battery.record_travel(Vec2::ZERO, 0.01);
+    battery.record_travel(Vec2::new(30.0, 40.0), 0.01);
+    assert!((battery.charge - 0.5).abs() < 1e-5);
+    assert!(!battery.needs_recharge(20.0, &config));
+    assert!(battery.needs_recharge(30.0, &config));
+    battery.record_travel(Vec2::new(300.0, 40.0), 0.01);
+    assert!(battery.is_empty());
+    battery.recharge(2.0);
+    assert!(battery.is_full());
+}
+
+// La nuit, ni la base ni les panneaux ne rechargent et les capteurs voient moins loin
+#[test]
+fn test_no_charging_at_night() {
+    let mut app = daylight_app(DaylightConfig {
+        day_length: 20.0,
+        daylight_ratio: 0.5,
+        night_sensor_factor: 0.5,
+        ..default()
+    });
+    run(&mut app, 12.0);
+    assert!(app.world.query::<&Sensor>().iter(&app.world).all(|sensor| sensor.attenuation == 0.5));
+
+    for mut battery in app.world.query::<&mut Battery>().iter_mut(&mut app.world) {
+        battery.charge = 0.5;
+    }
+    run(&mut app, 18.0);
+    assert!(app.world.query::<&Battery>().iter(&app.world).all(|battery| battery.charge == 0.5));
+
+    run(&mut app, 22.0);
+    assert!(app.world.query::<&Battery>().iter(&app.world).all(|battery| battery.charge > 0.5));
+    assert!(app.world.query::<&Sensor>().iter(&app.world).all(|sensor| sensor.attenuation == 1.0));
+}
+
+// Un explorateur qui consomme vite rentre se recharger puis repart explorer
+#[test]
+fn test_explorer_returns_to_charge() {
+    let mut app = daylight_app(DaylightConfig {
+        drain_per_distance: 0.002,
+        base_charge_rate: 0.25,
+        panel_charge_rate: 0.0,
+        ..default()
+    });
+    run(&mut app, 60.0);
+
+    let explorer = app.world.query_filtered::<Entity, With<Explorer>>().single(&app.world);
+    let transitions: Vec<(RobotState, RobotState)> = app
+        .world
+        .resource::<SeenTransitions>()
+        .0
+        .iter()
+        .filter(|transition| transition.entity == explorer)
+        .map(|transition| (transition.from, transition.to))
+        .collect();
+    let charging = transitions
+        .iter()
+        .position(|&(from, to)| from == RobotState::Returning && to == RobotState::Charging)
+        .expect("l'explorateur ne s'est jamais rechargé");
+    assert!(transitions[charging..].iter().any(|&(_, to)| to == RobotState::Exploring));
+    assert!(!app.world.get::<Battery>(explorer).unwrap().is_empty());
+    assert_eq!(app.world.resource::<SimulationStats>().illegal_transitions, 0);
+}
+
+// Des batteries qui se vident sans aucune recharge bloqueraient les robots en Charging
+#[test]
+fn test_draining_without_charging_rejected() {
+    let scenario = |panel_charge_rate: f32, base_charge_rate: f32| Scenario {
+        daylight: DaylightConfig {
+            drain_per_distance: 0.001,
+            panel_charge_rate,
+            base_charge_rate,
+            ..default()
+        },
+        ..Scenario::default()
+    };
+    assert!(matches!(scenario(0.0, 0.0).validate(), Err(ScenarioError::Invalid(_))));
+    assert!(scenario(0.0, 0.05).validate().is_ok());
+    assert!(scenario(0.005, 0.0).validate().is_ok());
+}
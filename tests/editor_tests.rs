@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use simulation_robots::editor::{edited_scenario, toggle_obstacle};
+use simulation_robots::headless::headless_app;
+use simulation_robots::pathfinding::PlannedPath;
+use simulation_robots::robots::{Deposit, GameMap, Miner, Resource};
+use simulation_robots::scenario::{grid_rows, MapConfig, MapGenerator, Scenario, ScenarioError};
+
+fn small_scenario() -> Scenario {
+    Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(9),
+            ..Scenario::default().map
+        },
+        ..Scenario::default()
+    }
+}
+
+#[test]
+fn test_toggle_obstacle() {
+    let mut game_map = MapConfig {
+        generator: MapGenerator::Empty,
+        ..small_scenario().map
+    }
+    .generate(9);
+    let position = Vec2::new(105.0, 95.0);
+    let cell = game_map.world_to_cell(position);
+
+    assert_eq!(toggle_obstacle(&mut game_map, position, &[]), Some(true));
+    assert!(game_map.is_obstacle_cell(cell));
+    assert_eq!(toggle_obstacle(&mut game_map, position, &[]), Some(false));
+    assert!(!game_map.is_obstacle_cell(cell));
+
+    // Un robot posé sur la case empêche d'y construire un mur
+    assert_eq!(toggle_obstacle(&mut game_map, position, &[(position + Vec2::X * 8.0, 6.0)]), None);
+    assert!(!game_map.is_obstacle_cell(cell));
+    assert_eq!(toggle_obstacle(&mut game_map, Vec2::new(5000.0, 0.0), &[]), None);
+}
+
+// La grille exportée redonne exactement la même carte
+#[test]
+fn test_exported_grid_roundtrip() {
+    let scenario = small_scenario();
+    let mut game_map = scenario.map.generate(9);
+    toggle_obstacle(&mut game_map, Vec2::new(-305.0, 205.0), &[]);
+
+    let deposits = [(Resource::Energy, Vec2::new(100.0, 0.0), 6), (Resource::Mineral, Vec2::new(-100.0, 0.0), 4)];
+    let edited = edited_scenario(&scenario, &game_map, &deposits, &[Vec2::new(0.0, 200.0)], scenario.robots.clone());
+    let reloaded = Scenario::from_ron(&edited.to_ron().unwrap()).unwrap();
+
+    let regenerated: GameMap = reloaded.map.generate(1234);
+    assert_eq!(regenerated.obstacles, game_map.obstacles);
+    let resources = reloaded.resources.place(&reloaded.map, &reloaded.base_positions(), 0);
+    assert_eq!(resources.energy_positions, vec![Vec2::new(100.0, 0.0)]);
+    assert_eq!(resources.mineral_positions, vec![Vec2::new(-100.0, 0.0)]);
+    assert_eq!(resources.scientific_sites, vec![Vec2::new(0.0, 200.0)]);
+    // Le minerai entamé garde sa quantité restante, l'énergie intacte repart pleine
+    assert_eq!(reloaded.resources.deposit_quantity(Vec2::new(-100.0, 0.0), Resource::Mineral), 4);
+    assert_eq!(reloaded.resources.deposit_quantity(Vec2::new(100.0, 0.0), Resource::Energy), 6);
+    let mut app = headless_app(reloaded.clone());
+    app.update();
+    let mut amounts: Vec<(u32, u32)> = app
+        .world
+        .query::<&Deposit>()
+        .iter(&app.world)
+        .map(|deposit| (deposit.remaining, deposit.initial))
+        .collect();
+    amounts.sort();
+    assert_eq!(amounts, vec![(4, 9), (6, 6)]);
+
+    let mut broken = reloaded.clone();
+    if let MapGenerator::Grid(rows) = &mut broken.map.generator {
+        rows[0].pop();
+    }
+    assert!(matches!(broken.validate(), Err(ScenarioError::Invalid(_))));
+    assert_eq!(grid_rows(&game_map).len(), 30);
+}
+
+// Modifier la carte oublie les chemins planifiés
+#[test]
+fn test_map_change_invalidates_paths() {
+    let mut app = headless_app(small_scenario());
+    app.update();
+
+    let mut miners = app.world.query_filtered::<&mut PlannedPath, With<Miner>>();
+    for mut path in miners.iter_mut(&mut app.world) {
+        path.set(Vec2::new(200.0, 150.0), vec![Vec2::new(100.0, 100.0), Vec2::new(200.0, 150.0)]);
+    }
+    app.update();
+    assert!(miners.iter(&app.world).all(|path| path.goal.is_some()));
+
+    toggle_obstacle(&mut app.world.resource_mut::<GameMap>(), Vec2::new(-305.0, 205.0), &[]);
+    app.update();
+    assert!(miners.iter(&app.world).all(|path| path.goal.is_none() && path.waypoints.is_empty()));
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use simulation_robots::collision::circle_overlaps_obstacles;
+use simulation_robots::environment::{
+    rockfall_cells, rockfall_is_clear, storm_attenuation, DustStorm, EnvironmentClock, EnvironmentConfig, EnvironmentEvent,
+};
+use simulation_robots::headless::headless_app;
+use simulation_robots::robots::{Base, GameMap, RobotBody};
+use simulation_robots::scenario::{MapConfig, MapGenerator, Scenario};
+use simulation_robots::sensor::Sensor;
+use simulation_robots::stats::SimulationStats;
+
+#[derive(Resource, Default)]
+struct SeenEvents(Vec<EnvironmentEvent>);
+
+fn record_events(mut events: EventReader<EnvironmentEvent>, mut seen: ResMut<SeenEvents>) {
+    seen.0.extend(events.iter().cloned());
+}
+
+fn eventful_app(environment: EnvironmentConfig) -> App {
+    let mut app = headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(8),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        environment,
+        ..Scenario::default()
+    });
+    app.init_resource::<SeenEvents>().add_systems(Update, record_events);
+    app
+}
+
+fn run(app: &mut App, seconds: f32) {
+    while app.world.resource::<SimulationStats>().elapsed < seconds {
+        app.update();
+    }
+}
+
+#[test]
+fn test_rockfall_shape_and_storm_attenuation() {
+    let game_map = MapConfig {
+        generator: MapGenerator::Empty,
+        ..Scenario::default().map
+    }
+    .generate(1);
+    assert_eq!(rockfall_cells(&game_map, IVec2::new(10, 10), 1).len(), 5);
+    assert_eq!(rockfall_cells(&game_map, IVec2::new(0, 0), 1).len(), 3);
+    let large = rockfall_cells(&game_map, IVec2::new(10, 10), 4);
+    let edge = game_map.cell_center(IVec2::new(14, 10));
+    assert!(!rockfall_is_clear(&game_map, &large, &[(edge + Vec2::new(15.0, 0.0), 10.0)]));
+    assert!(rockfall_is_clear(&game_map, &large, &[(edge + Vec2::new(30.0, 0.0), 10.0)]));
+
+    let storms = [(Vec2::new(100.0, 0.0), 50.0)];
+    assert_eq!(storm_attenuation(Vec2::new(120.0, 0.0), &storms, 0.25), 0.25);
+    assert_eq!(storm_attenuation(Vec2::ZERO, &storms, 0.25), 1.0);
+
+    let sensor = Sensor {
+        range: 100.0,
+        attenuation: 0.25,
+        ..default()
+    };
+    assert_eq!(sensor.effective_range(), 25.0);
+    assert!(!sensor.can_see(Vec2::ZERO, Vec2::X, Vec2::new(50.0, 0.0), &game_map));
+}
+
+// Les éboulements ajoutent des murs, rendent les cases inexplorées et les nouveaux gisements apparaissent
+#[test]
+fn test_rockfalls_and_new_deposits() {
+    let mut app = eventful_app(EnvironmentConfig {
+        event_interval: 4.0,
+        storm_weight: 0,
+        ..default()
+    });
+    run(&mut app, 80.0);
+
+    let events = &app.world.resource::<SeenEvents>().0;
+    let rockfall: Vec<IVec2> = events
+        .iter()
+        .filter_map(|event| match event {
+            EnvironmentEvent::Rockfall { cells } => Some(cells.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert!(!rockfall.is_empty());
+    assert!(events.iter().any(|event| matches!(event, EnvironmentEvent::NewDeposit { .. })));
+    assert!(!events.iter().any(|event| matches!(event, EnvironmentEvent::DustStorm { .. })));
+
+    let game_map = app.world.resource::<GameMap>();
+    assert!(rockfall.iter().all(|cell| game_map.is_obstacle_cell(*cell)));
+}
+
+// Dans une tempête, la portée des capteurs baisse puis revient à la fin
+#[test]
+fn test_dust_storm_reduces_sensors() {
+    let mut app = eventful_app(EnvironmentConfig {
+        event_interval: 2.0,
+        rockfall_weight: 0,
+        deposit_weight: 0,
+        storm_radius: 1000.0,
+        storm_duration: 5.0,
+        storm_sensor_factor: 0.5,
+        ..default()
+    });
+    while app.world.query::<&DustStorm>().iter(&app.world).next().is_none() {
+        app.update();
+    }
+    app.update();
+    assert!(app.world.query::<&Sensor>().iter(&app.world).all(|sensor| sensor.attenuation == 0.5));
+
+    app.world.resource_mut::<EnvironmentClock>().next_event_at = Some(f32::MAX);
+    let storm_end = app.world.query::<&DustStorm>().iter(&app.world).next().unwrap().ends_at;
+    run(&mut app, storm_end + 0.1);
+    assert_eq!(app.world.query::<&DustStorm>().iter(&app.world).count(), 0);
+    assert!(app.world.query::<&Sensor>().iter(&app.world).all(|sensor| sensor.attenuation == 1.0));
+}
+
+// Un éboulement de grand rayon ne tombe jamais sur un robot ou une base, même loin de son centre
+#[test]
+fn test_large_rockfalls_spare_robots_and_bases() {
+    let mut app = eventful_app(EnvironmentConfig {
+        event_interval: 3.0,
+        storm_weight: 0,
+        deposit_weight: 0,
+        rockfall_radius: 4,
+        ..default()
+    });
+    let mut seen = 0;
+    while app.world.resource::<SimulationStats>().elapsed < 90.0 {
+        app.update();
+        let count = app.world.resource::<SeenEvents>().0.len();
+        if count == seen {
+            continue;
+        }
+        seen = count;
+        let mut circles: Vec<(Vec2, f32)> = app
+            .world
+            .query::<(&Transform, &RobotBody)>()
+            .iter(&app.world)
+            .map(|(transform, body)| (transform.translation.truncate(), body.radius))
+            .collect();
+        circles.extend(
+            app.world
+                .query_filtered::<&Transform, With<Base>>()
+                .iter(&app.world)
+                .map(|transform| (transform.translation.truncate(), 15.0)),
+        );
+        let game_map = app.world.resource::<GameMap>();
+        for (position, radius) in circles {
+            assert!(!circle_overlaps_obstacles(position, radius, game_map), "éboulement sur {:?}", position);
+        }
+    }
+    assert!(seen > 0);
+}
@@ -0,0 +1,296 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use simulation_robots::robots::{
+    Cargo, Deposit, DiscoveredResource, ExplorationMode, Explorer, GameMap, MapResources, Miner,
+    Resource, RobotBody, Station,
+};
+use simulation_robots::headless;
+use simulation_robots::motion::{wrap_angle, Kinematics};
+use simulation_robots::scenario::{
+    BaseConfig, MapConfig, MapGenerator, ResourcePlacement, RobotKind, RobotSpec, Scenario,
+};
+use simulation_robots::state::RobotState;
+use simulation_robots::stats::SimulationStats;
+use simulation_robots::watchdog::RobotStuck;
+
+fn headless_app_with_mode(seed: u32, exploration_mode: ExplorationMode) -> App {
+    headless::headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(seed),
+            ..Scenario::default().map
+        },
+        exploration_mode,
+        ..Scenario::default()
+    })
+}
+
+fn headless_app(seed: u32) -> App {
+    headless_app_with_mode(seed, ExplorationMode::default())
+}
+
+// Envoie les mineurs sur une ressource sans attendre l'explorateur
+fn dispatch_miners(app: &mut App) {
+    let target = app.world.resource::<MapResources>().energy_positions[3];
+    app.world.resource_mut::<DiscoveredResource>().position = Some(target);
+
+    let mut miners = app.world.query_filtered::<&mut RobotState, With<Miner>>();
+    for mut state in miners.iter_mut(&mut app.world) {
+        *state = RobotState::MovingTo(target);
+    }
+}
+
+// Aucun robot ne doit en chevaucher un autre pendant la simulation
+#[test]
+fn test_robots_do_not_overlap() {
+    let mut app = headless_app(42);
+    app.update();
+    dispatch_miners(&mut app);
+
+    let tolerance = 1.0;
+    for frame in 0..900 {
+        app.update();
+
+        let robots: Vec<(Vec2, f32)> = app.world
+            .query::<(&Transform, &RobotBody)>()
+            .iter(&app.world)
+            .map(|(transform, body)| (transform.translation.truncate(), body.radius))
+            .collect();
+
+        for (i, (pos_a, radius_a)) in robots.iter().enumerate() {
+            for (pos_b, radius_b) in robots.iter().skip(i + 1) {
+                let distance = pos_a.distance(*pos_b);
+                assert!(
+                    distance >= radius_a + radius_b - tolerance,
+                    "robots superposés à la frame {}: distance {:.2}",
+                    frame,
+                    distance
+                );
+            }
+        }
+    }
+
+    assert_eq!(app.world.resource::<SimulationStats>().illegal_transitions, 0);
+}
+
+// Un groupe de mineurs tassés contre le bord de la carte se sépare en quelques frames
+#[test]
+fn test_packed_robots_separate_quickly() {
+    let mut scenario = Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(1),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        ..Scenario::default()
+    };
+    scenario.robots[1] = RobotSpec {
+        kind: RobotKind::Miner,
+        position: (-392.0, 0.0),
+        count: 6,
+        spacing: (3.0, 0.0),
+        radius: None,
+        motion: None,
+        sensor: None,
+    };
+    let mut app = headless::headless_app(scenario);
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let miners: Vec<(Vec2, f32)> = app.world
+        .query_filtered::<(&Transform, &RobotBody), With<Miner>>()
+        .iter(&app.world)
+        .map(|(transform, body)| (transform.translation.truncate(), body.radius))
+        .collect();
+    for (i, (pos_a, radius_a)) in miners.iter().enumerate() {
+        for (pos_b, radius_b) in miners.iter().skip(i + 1) {
+            assert!(pos_a.distance(*pos_b) >= radius_a + radius_b - 1.0);
+        }
+    }
+}
+
+// Le gisement est vidé en plusieurs allers-retours et tout arrive à la station
+#[test]
+fn test_deposit_depleted_over_multiple_trips() {
+    let mut app = headless_app(42);
+    app.update();
+
+    let explorer = app.world
+        .query_filtered::<Entity, With<Explorer>>()
+        .single(&app.world);
+    app.world.despawn(explorer);
+    dispatch_miners(&mut app);
+
+    let quantity = Resource::Energy.initial_quantity();
+    let mut smallest_remaining = quantity;
+
+    for _ in 0..6000 {
+        app.update();
+
+        let remaining = app.world
+            .query::<(&Transform, &Deposit)>()
+            .iter(&app.world)
+            .find(|(transform, _)| transform.translation.truncate() == Vec2::new(150.0, 0.0))
+            .map(|(_, deposit)| deposit.remaining)
+            .unwrap_or(0);
+        smallest_remaining = smallest_remaining.min(remaining);
+
+        let idle_miners = app.world
+            .query_filtered::<&RobotState, With<Miner>>()
+            .iter(&app.world)
+            .filter(|state| **state == RobotState::Idle)
+            .count();
+        if app.world.resource::<Station>().energy == quantity && idle_miners == 3 {
+            break;
+        }
+    }
+
+    let station = app.world.resource::<Station>();
+    assert_eq!(smallest_remaining, 0);
+    assert_eq!(station.energy, quantity);
+    assert!(station.known_deposits.is_empty());
+    assert!(app.world.resource::<DiscoveredResource>().position.is_none());
+}
+
+// Temps de simulation nécessaire pour ramener `units` unités à la station
+fn time_to_collect(seed: u32, exploration_mode: ExplorationMode, units: usize) -> f32 {
+    let mut app = headless_app_with_mode(seed, exploration_mode);
+
+    for _ in 0..30_000 {
+        app.update();
+        if let Some(time) = app.world.resource::<SimulationStats>().time_to_collect(units) {
+            return time;
+        }
+    }
+    panic!("seed {}: {} unités non collectées en mode {:?}", seed, units, exploration_mode);
+}
+
+// L'explorateur qui continue pendant la collecte réduit le temps total
+#[test]
+fn test_concurrent_exploration_collects_faster() {
+    let seeds = [1, 2, 3];
+    let units = 20;
+
+    let serialized: f32 = seeds.iter()
+        .map(|&seed| time_to_collect(seed, ExplorationMode::Serialized, units))
+        .sum();
+    let concurrent: f32 = seeds.iter()
+        .map(|&seed| time_to_collect(seed, ExplorationMode::Concurrent, units))
+        .sum();
+
+    println!("Temps de collecte: séquentiel {:.1}s, concurrent {:.1}s", serialized, concurrent);
+    assert!(concurrent < serialized);
+}
+
+// Des mineurs envoyés vers un gisement emmuré sont détectés bloqués puis abandonnent
+#[test]
+fn test_stuck_miners_recover_and_report_failure() {
+    let mut app = headless_app(42);
+    app.update();
+
+    let explorer = app.world
+        .query_filtered::<Entity, With<Explorer>>()
+        .single(&app.world);
+    app.world.despawn(explorer);
+
+    let target = app.world.resource::<MapResources>().energy_positions[3];
+    {
+        let mut game_map = app.world.resource_mut::<GameMap>();
+        let center = game_map.world_to_cell(target);
+        for y in -3..=3_i32 {
+            for x in -3..=3_i32 {
+                if x.abs() == 3 || y.abs() == 3 {
+                    let cell = center + IVec2::new(x, y);
+                    game_map.obstacles[cell.y as usize][cell.x as usize] = true;
+                }
+            }
+        }
+    }
+    app.world.resource_mut::<Station>().register_deposit(Entity::PLACEHOLDER, target, Resource::Energy, 6);
+    dispatch_miners(&mut app);
+
+    let mut stuck_events = 0;
+    for _ in 0..6000 {
+        app.update();
+
+        let events = app.world.resource::<Events<RobotStuck>>();
+        stuck_events += events.get_reader().iter(events).count();
+
+        let idle_miners = app.world
+            .query_filtered::<&RobotState, With<Miner>>()
+            .iter(&app.world)
+            .filter(|state| **state == RobotState::Idle)
+            .count();
+        if app.world.resource::<SimulationStats>().recovery_failures > 0 && idle_miners == 3 {
+            break;
+        }
+    }
+
+    let stats = app.world.resource::<SimulationStats>();
+    assert!(stats.recovery_failures > 0);
+    assert!(stats.stuck_events >= 3);
+    assert!(stuck_events > 0);
+    assert_eq!(stats.illegal_transitions, 0);
+    assert!(app.world.resource::<Station>().known_deposits.is_empty());
+    assert!(app.world.resource::<DiscoveredResource>().position.is_none());
+}
+
+// Les robots tournent progressivement au lieu de changer de cap instantanément
+#[test]
+fn test_robot_heading_changes_smoothly() {
+    let mut app = headless_app(42);
+    app.update();
+    dispatch_miners(&mut app);
+
+    let mut previous: HashMap<Entity, f32> = HashMap::new();
+    for _ in 0..600 {
+        app.update();
+
+        for (entity, kinematics) in app.world.query::<(Entity, &Kinematics)>().iter(&app.world) {
+            if let Some(heading) = previous.insert(entity, kinematics.heading) {
+                let turn = wrap_angle(kinematics.heading - heading).abs();
+                assert!(turn <= kinematics.config.max_turn_rate / 30.0 + 1e-3, "virage de {:.3} rad", turn);
+                assert!(kinematics.speed <= kinematics.config.max_speed + 1e-3);
+            }
+        }
+    }
+}
+
+// Avec plusieurs bases, un mineur chargé décharge à la plus proche
+#[test]
+fn test_miner_unloads_at_nearest_base() {
+    let mut scenario = Scenario {
+        map: MapConfig {
+            width: 1000.0,
+            height: 800.0,
+            seed: Some(1),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        bases: vec![BaseConfig { position: (-300.0, 0.0) }, BaseConfig { position: (300.0, 0.0) }],
+        resources: ResourcePlacement::Fixed { energy: vec![], minerals: vec![], scientific_sites: vec![], remaining: vec![] },
+        ..Scenario::default()
+    };
+    scenario.robots[1] = RobotSpec { position: (100.0, -40.0), count: 1, ..scenario.robots[1].clone() };
+    let mut app = headless::headless_app(scenario);
+    app.update();
+
+    let miner = app.world.query_filtered::<Entity, With<Miner>>().single(&app.world);
+    *app.world.get_mut::<Cargo>(miner).unwrap() = Cargo { resource: Some(Resource::Mineral), amount: 2 };
+    *app.world.get_mut::<RobotState>(miner).unwrap() = RobotState::Returning;
+
+    for _ in 0..600 {
+        app.update();
+        if app.world.resource::<Station>().minerals == 2 {
+            break;
+        }
+    }
+
+    assert_eq!(app.world.resource::<Station>().minerals, 2);
+    let position = app.world.get::<Transform>(miner).unwrap().translation.truncate();
+    assert!(position.distance(Vec2::new(300.0, 0.0)) < 30.0, "déchargé en {:?}", position);
+}
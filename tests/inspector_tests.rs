@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use simulation_robots::headless::headless_app;
+use simulation_robots::inspector::{
+    record_robot_events, robot_panel_text, station_panel_text, RobotDetails, RobotEventLog, EVENT_HISTORY,
+};
+use simulation_robots::robots::{DiscoveredResource, Miner, Resource, Station};
+use simulation_robots::scenario::{MapConfig, Scenario};
+use simulation_robots::state::RobotState;
+
+#[test]
+fn test_event_log_keeps_recent_entries() {
+    let mut log = RobotEventLog::default();
+    let entity = Entity::from_raw(7);
+    for i in 0..EVENT_HISTORY + 3 {
+        log.record(entity, format!("événement {}", i));
+    }
+
+    let recent = log.recent(entity);
+    assert_eq!(recent.len(), EVENT_HISTORY);
+    assert_eq!(recent.first().unwrap(), "événement 3");
+    assert!(log.recent(Entity::from_raw(8)).is_empty());
+}
+
+#[test]
+fn test_panel_texts() {
+    let details = RobotDetails {
+        entity: Entity::from_raw(3),
+        kind: "Mineur",
+        state: RobotState::MovingTo(Vec2::new(100.0, 50.0)),
+        position: Vec2::new(10.0, -4.0),
+        target: Some(Vec2::new(100.0, 50.0)),
+        path_length: Some((2, 120.0)),
+        cargo: Some("vide".into()),
+        battery: Some(0.42),
+        events: vec!["[1.0s] Idle -> MovingTo".into()],
+    };
+    let text = robot_panel_text(&details);
+    assert!(text.contains("État : MovingTo"));
+    assert!(text.contains("Cible : (100, 50)"));
+    assert!(text.contains("Chemin : 2 étapes, 120 px"));
+    assert!(text.contains("Batterie : 42%"));
+    assert!(text.contains("Idle -> MovingTo"));
+
+    let mut station = Station {
+        minerals: 4,
+        ..default()
+    };
+    station.register_deposit(Entity::from_raw(1), Vec2::new(20.0, 30.0), Resource::Mineral, 9);
+    let text = station_panel_text(&station);
+    assert!(text.contains("Minerais : 4"));
+    assert!(text.contains("Gisements connus : 1"));
+    assert!(text.contains("Mineral (20, 30) reste 9"));
+}
+
+// Les transitions émises par la simulation sont rattachées au bon robot
+#[test]
+fn test_events_recorded_per_robot() {
+    let mut app = headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(42),
+            ..Scenario::default().map
+        },
+        ..Scenario::default()
+    });
+    app.init_resource::<RobotEventLog>().add_systems(Update, record_robot_events);
+    app.update();
+
+    let target = Vec2::new(150.0, 0.0);
+    app.world.resource_mut::<DiscoveredResource>().position = Some(target);
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let miners: Vec<Entity> = app.world.query_filtered::<Entity, With<Miner>>().iter(&app.world).collect();
+    let log = app.world.resource::<RobotEventLog>();
+    for miner in miners {
+        assert!(log.recent(miner).iter().any(|event| event.contains("Idle -> MovingTo")), "{:?}", log.recent(miner));
+    }
+}
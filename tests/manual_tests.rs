@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use simulation_robots::headless::headless_app;
+use simulation_robots::manual::{ManualControl, ManuallyControlled};
+use simulation_robots::robots::{Cargo, Deposit, Explorer, Miner, Resource, Station};
+use simulation_robots::scenario::{MapConfig, MapGenerator, ResourcePlacement, RobotKind, RobotSpec, Scenario};
+use simulation_robots::state::RobotState;
+use simulation_robots::stats::SimulationStats;
+
+// Carte vide 800x600 avec un mur vertical en x = 200..220 et un gisement derrière
+fn walled_scenario() -> Scenario {
+    let mut rows = vec![".".repeat(40); 30];
+    for row in rows.iter_mut().take(25).skip(5) {
+        row.replace_range(30..31, "#");
+    }
+    Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(1),
+            generator: MapGenerator::Grid(rows),
+            ..Scenario::default().map
+        },
+        robots: vec![RobotSpec {
+            kind: RobotKind::Explorer,
+            position: (0.0, 50.0),
+            count: 1,
+            spacing: (0.0, 0.0),
+            radius: None,
+            motion: None,
+            sensor: None,
+        }],
+        resources: ResourcePlacement::Fixed {
+            energy: vec![],
+            minerals: vec![(-250.0, 50.0)],
+            scientific_sites: vec![],
+            remaining: vec![],
+        },
+        ..Scenario::default()
+    }
+}
+
+fn robot_position(app: &mut App, entity: Entity) -> Vec2 {
+    app.world.get::<Transform>(entity).unwrap().translation.truncate()
+}
+
+fn explorer(app: &mut App) -> (Entity, Vec2) {
+    let (entity, transform) = app
+        .world
+        .query_filtered::<(Entity, &Transform), With<Explorer>>()
+        .single(&app.world);
+    (entity, transform.translation.truncate())
+}
+
+#[test]
+fn test_manual_robot_follows_input_and_walls() {
+    let mut app = headless_app(walled_scenario());
+    app.update();
+    let (entity, start) = explorer(&mut app);
+
+    app.world.insert_resource(ManualControl {
+        entity: Some(entity),
+        direction: Vec2::X,
+    });
+    for _ in 0..150 {
+        app.update();
+    }
+
+    assert!(app.world.get::<ManuallyControlled>(entity).is_some());
+    let (_, position) = explorer(&mut app);
+    assert!(position.x > start.x + 150.0, "{:?}", position);
+    assert!(position.x < 200.0, "le mur a été traversé: {:?}", position);
+    assert!((position.y - start.y).abs() < 1.0);
+}
+
+// Le robot piloté découvre toujours les ressources, puis repart seul une fois relâché
+#[test]
+fn test_manual_explorer_discovers_and_is_released() {
+    let mut app = headless_app(walled_scenario());
+    app.update();
+    let (entity, _) = explorer(&mut app);
+
+    app.world.insert_resource(ManualControl {
+        entity: Some(entity),
+        direction: Vec2::NEG_X,
+    });
+    for _ in 0..120 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<SimulationStats>().discoveries, 1);
+    assert_eq!(app.world.resource::<Station>().known_deposits.len(), 1);
+
+    app.world.insert_resource(ManualControl::default());
+    let (_, released_at) = explorer(&mut app);
+    for _ in 0..60 {
+        app.update();
+    }
+    assert!(app.world.get::<ManuallyControlled>(entity).is_none());
+    assert_eq!(*app.world.get::<RobotState>(entity).unwrap(), RobotState::Exploring);
+    let (_, position) = explorer(&mut app);
+    assert!(position.distance(released_at) > 20.0);
+}
+
+// Un mineur piloté charge sur un gisement connu de la station et décharge à la base, sans garder d'ancienne cible
+#[test]
+fn test_manual_miner_collects_and_unloads() {
+    let deposit_position = Vec2::new(-150.0, -20.0);
+    let mut scenario = walled_scenario();
+    scenario.robots[0].kind = RobotKind::Miner;
+    scenario.robots[0].position = (-60.0, -20.0);
+    scenario.resources = ResourcePlacement::Fixed {
+        energy: vec![],
+        minerals: vec![(deposit_position.x, deposit_position.y)],
+        scientific_sites: vec![],
+        remaining: vec![],
+    };
+    let mut app = headless_app(scenario);
+    app.update();
+    let miner = app.world.query_filtered::<Entity, With<Miner>>().single(&app.world);
+    let (deposit, remaining) = {
+        let (entity, deposit) = app.world.query::<(Entity, &Deposit)>().single(&app.world);
+        (entity, deposit.remaining)
+    };
+
+    // Tant que la station ne connaît pas le gisement, s'y arrêter ne sert à rien
+    app.world.insert_resource(ManualControl {
+        entity: Some(miner),
+        direction: Vec2::NEG_X,
+    });
+    while robot_position(&mut app, miner).x > deposit_position.x + 20.0 {
+        app.update();
+    }
+    app.world.resource_mut::<ManualControl>().direction = Vec2::ZERO;
+    for _ in 0..30 {
+        app.update();
+    }
+    let stopped_at = robot_position(&mut app, miner);
+    assert!(stopped_at.distance(deposit_position) < 15.0, "{:?}", stopped_at);
+    assert_eq!(app.world.get::<Cargo>(miner).unwrap().amount, 0);
+    assert_eq!(*app.world.get::<RobotState>(miner).unwrap(), RobotState::Idle);
+
+    app.world
+        .resource_mut::<Station>()
+        .register_deposit(deposit, deposit_position, Resource::Mineral, remaining);
+    for _ in 0..300 {
+        app.update();
+    }
+    assert_eq!(app.world.get::<Cargo>(miner).unwrap().amount, 1);
+    assert_eq!(*app.world.get::<RobotState>(miner).unwrap(), RobotState::Returning);
+
+    app.world.resource_mut::<ManualControl>().direction = Vec2::X;
+    while robot_position(&mut app, miner).x < -5.0 {
+        app.update();
+    }
+    assert_eq!(*app.world.get::<RobotState>(miner).unwrap(), RobotState::Returning);
+    app.world.resource_mut::<ManualControl>().direction = Vec2::ZERO;
+    for _ in 0..30 {
+        app.update();
+    }
+    assert_eq!(app.world.get::<Cargo>(miner).unwrap().amount, 0);
+    assert_eq!(app.world.resource::<Station>().minerals, 1);
+    assert_eq!(app.world.resource::<SimulationStats>().minerals_collected, 1);
+    assert_eq!(*app.world.get::<RobotState>(miner).unwrap(), RobotState::Idle);
+    assert_eq!(app.world.resource::<SimulationStats>().illegal_transitions, 0);
+}
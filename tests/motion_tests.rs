@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use simulation_robots::motion::{wrap_angle, Kinematics, MotionConfig};
+
+const DELTA: f32 = 1.0 / 30.0;
+
+// La vitesse ne dépasse jamais le maximum et augmente au plus de acceleration * delta
+#[test]
+fn test_acceleration_is_bounded() {
+    let mut kinematics = Kinematics::new(MotionConfig::MINER);
+    let mut previous_speed = 0.0;
+
+    for _ in 0..120 {
+        kinematics.steer(Some(Vec2::X), 500.0, DELTA);
+        assert!(kinematics.speed - previous_speed <= MotionConfig::MINER.acceleration * DELTA + 1e-4);
+        assert!(kinematics.speed <= MotionConfig::MINER.max_speed + 1e-4);
+        previous_speed = kinematics.speed;
+    }
+    assert!((kinematics.speed - MotionConfig::MINER.max_speed).abs() < 1e-3);
+
+    // Sans consigne le robot freine jusqu'à l'arrêt
+    for _ in 0..120 {
+        kinematics.steer(None, 0.0, DELTA);
+    }
+    assert_eq!(kinematics.speed, 0.0);
+}
+
+// Le cap tourne au plus à max_turn_rate et le robot ralentit tant qu'il n'est pas aligné
+#[test]
+fn test_turn_rate_is_bounded() {
+    let config = MotionConfig::EXPLORER;
+    let mut kinematics = Kinematics::new(config);
+    for _ in 0..60 {
+        kinematics.steer(Some(Vec2::X), config.max_speed, DELTA);
+    }
+
+    let mut frames = 0;
+    while wrap_angle(std::f32::consts::PI - kinematics.heading).abs() > 1e-3 {
+        let previous = kinematics.heading;
+        let motion = kinematics.steer(Some(Vec2::NEG_X), config.max_speed, DELTA);
+        assert!(wrap_angle(kinematics.heading - previous).abs() <= config.max_turn_rate * DELTA + 1e-4);
+        assert!(motion.length() <= config.max_speed * DELTA + 1e-4);
+        frames += 1;
+        assert!(frames < 100);
+    }
+
+    // Un demi-tour prend au moins PI / max_turn_rate secondes
+    assert!(frames as f32 * DELTA >= std::f32::consts::PI / config.max_turn_rate - DELTA);
+}
+
+#[test]
+fn test_braking_speed() {
+    let kinematics = Kinematics::new(MotionConfig::MINER);
+    assert_eq!(kinematics.braking_speed(0.0), 0.0);
+    assert!(kinematics.braking_speed(5.0) < kinematics.braking_speed(20.0));
+    assert_eq!(kinematics.braking_speed(10_000.0), MotionConfig::MINER.max_speed);
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use simulation_robots::headless::{headless_app, run_mission};
+use simulation_robots::objectives::{objective_progress, MissionOutcome, MissionStatus};
+use simulation_robots::scenario::{
+    MapConfig, MapGenerator, Objective, ResourcePlacement, Scenario, ScenarioError, StopConditions,
+};
+use simulation_robots::stats::SimulationStats;
+
+fn scenario(objectives: Vec<Objective>, time_limit: f32) -> Scenario {
+    Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(3),
+            ..Scenario::default().map
+        },
+        stop_conditions: StopConditions {
+            time_limit: Some(time_limit),
+            objectives,
+        },
+        ..Scenario::default()
+    }
+}
+
+#[test]
+fn test_objective_progress() {
+    let stats = SimulationStats {
+        minerals_collected: 4,
+        energy_collected: 1,
+        sites_analyzed: 2,
+        ..default()
+    };
+
+    assert!(objective_progress(&Objective::CollectMinerals(4), &stats, 0.0, 0).is_met());
+    assert!(!objective_progress(&Objective::CollectEnergy(2), &stats, 0.0, 0).is_met());
+    assert!(objective_progress(&Objective::ExploreRatio(0.3), &stats, 0.35, 0).is_met());
+    assert!(!objective_progress(&Objective::AnalyzeScientificSites, &stats, 0.0, 3).is_met());
+    assert!(objective_progress(&Objective::AnalyzeScientificSites, &stats, 0.0, 2).is_met());
+}
+
+#[test]
+fn test_mission_succeeds_when_objectives_met() {
+    let mut app = headless_app(scenario(vec![Objective::ExploreRatio(0.02)], 60.0));
+    let status = run_mission(&mut app, 60.0);
+
+    assert_eq!(status.outcome, Some(MissionOutcome::Success));
+    assert_eq!(status.exit_code(), 0);
+    assert!(status.ended_at.unwrap() < 60.0);
+}
+
+// À la limite de temps la mission échoue et la simulation s'arrête
+#[test]
+fn test_mission_fails_on_time_limit() {
+    let mut app = headless_app(scenario(vec![Objective::CollectMinerals(1000)], 2.0));
+    let status = run_mission(&mut app, 10.0);
+
+    assert!(matches!(status.outcome, Some(MissionOutcome::Failure(_))));
+    assert_eq!(status.exit_code(), 2);
+
+    let ended_at = app.world.resource::<SimulationStats>().elapsed;
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<SimulationStats>().elapsed, ended_at);
+    assert!(!app.world.resource::<MissionStatus>().is_running());
+}
+
+#[test]
+fn test_scientific_sites_analyzed() {
+    let mut scenario = scenario(vec![Objective::AnalyzeScientificSites], 60.0);
+    scenario.map.generator = MapGenerator::Empty;
+    scenario.resources = ResourcePlacement::Fixed {
+        energy: vec![],
+        minerals: vec![],
+        scientific_sites: vec![(60.0, 50.0), (-60.0, 50.0)],
+        remaining: vec![],
+    };
+
+    let mut app = headless_app(scenario);
+    let status = run_mission(&mut app, 60.0);
+
+    assert_eq!(status.outcome, Some(MissionOutcome::Success));
+    assert_eq!(app.world.resource::<SimulationStats>().sites_analyzed, 2);
+}
+
+// Un objectif d'analyse sans site serait atteint au premier pas: le scénario est refusé
+#[test]
+fn test_scientific_objective_requires_sites() {
+    let mut scenario = scenario(vec![Objective::AnalyzeScientificSites], 60.0);
+    scenario.resources = ResourcePlacement::Fixed {
+        energy: vec![],
+        minerals: vec![(100.0, 100.0)],
+        scientific_sites: vec![],
+        remaining: vec![],
+    };
+    assert!(matches!(scenario.validate(), Err(ScenarioError::Invalid(_))));
+
+    scenario.resources = ResourcePlacement::Random {
+        energy: 2,
+        minerals: 2,
+        scientific_sites: 0,
+        min_distance_from_base: 50.0,
+    };
+    assert!(matches!(scenario.validate(), Err(ScenarioError::Invalid(_))));
+
+    scenario.resources = ResourcePlacement::Random {
+        energy: 2,
+        minerals: 2,
+        scientific_sites: 1,
+        min_distance_from_base: 50.0,
+    };
+    assert!(scenario.validate().is_ok());
+}
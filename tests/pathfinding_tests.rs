@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use simulation_robots::collision::first_hit;
+use simulation_robots::pathfinding::{find_path, PlannedPath};
+use simulation_robots::robots::GameMap;
+
+fn map_with_cells(cells: &[(usize, usize)]) -> GameMap {
+    let mut obstacles = vec![vec![false; 20]; 20];
+    for &(x, y) in cells {
+        obstacles[y][x] = true;
+    }
+    GameMap {
+        size: Vec2::new(400.0, 400.0),
+        cell_size: 20.0,
+        obstacles,
+        seed: 0,
+    }
+}
+
+// Un piège en U entre le robot et la cible est contourné
+#[test]
+fn test_path_around_concave_trap() {
+    let mut cells = Vec::new();
+    for i in 6..14 {
+        cells.push((i, 6));
+        cells.push((6, i));
+        cells.push((13, i));
+    }
+    let game_map = map_with_cells(&cells);
+
+    let from = Vec2::new(0.0, 20.0);
+    let to = Vec2::new(0.0, -150.0);
+    let path = find_path(from, to, 6.0, &game_map).expect("chemin introuvable");
+
+    assert_eq!(*path.last().unwrap(), to);
+    let mut previous = from;
+    for &waypoint in &path {
+        assert!(first_hit(previous, waypoint - previous, 6.0, &game_map).is_none());
+        previous = waypoint;
+    }
+}
+
+// Une cible entièrement emmurée n'a pas de chemin
+#[test]
+fn test_no_path_to_enclosed_goal() {
+    let mut cells = Vec::new();
+    for i in 8..=12 {
+        cells.extend([(i, 8), (i, 12), (8, i), (12, i)]);
+    }
+    let game_map = map_with_cells(&cells);
+
+    assert!(find_path(Vec2::new(-150.0, -150.0), Vec2::new(10.0, 10.0), 6.0, &game_map).is_none());
+}
+
+// Les points de passage atteints sont consommés et un nouvel objectif efface le chemin
+#[test]
+fn test_planned_path_waypoints() {
+    let goal = Vec2::new(100.0, 0.0);
+    let mut path = PlannedPath::default();
+    path.set(goal, vec![Vec2::new(0.0, 50.0), goal]);
+
+    assert_eq!(path.next_waypoint(Vec2::ZERO, goal), Vec2::new(0.0, 50.0));
+    assert_eq!(path.next_waypoint(Vec2::new(0.0, 45.0), goal), goal);
+    assert_eq!(path.next_waypoint(Vec2::ZERO, Vec2::new(-100.0, 0.0)), Vec2::new(-100.0, 0.0));
+    assert!(path.waypoints.is_empty());
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use simulation_robots::headless::headless_app;
+use simulation_robots::production::{next_robot, ProductionConfig, ProductionPolicy, ProductionQueue, RobotCost};
+use simulation_robots::robots::{Explorer, Miner, Station};
+use simulation_robots::scenario::{MapConfig, MapGenerator, RobotKind, Scenario};
+use simulation_robots::stats::SimulationStats;
+
+fn stocked(energy: u32, minerals: u32) -> Station {
+    Station {
+        energy,
+        minerals,
+        ..default()
+    }
+}
+
+#[test]
+fn test_ratio_policy_picks_missing_kind() {
+    let config = ProductionConfig {
+        policy: ProductionPolicy::KeepRatio { explorers: 1, miners: 2 },
+        ..default()
+    };
+    let rich = stocked(100, 100);
+    assert_eq!(next_robot(&config, 1, 1, &rich), Some(RobotKind::Miner));
+    assert_eq!(next_robot(&config, 1, 3, &rich), Some(RobotKind::Explorer));
+    assert_eq!(next_robot(&config, 2, 4, &rich), Some(RobotKind::Miner));
+
+    // Pas assez de stock pour l'explorateur voulu: on attend plutôt que de construire autre chose
+    assert_eq!(next_robot(&config, 1, 3, &stocked(7, 100)), None);
+    assert_eq!(next_robot(&config, 4, 8, &rich), None);
+    assert_eq!(next_robot(&ProductionConfig::default(), 1, 1, &rich), None);
+}
+
+// Un type demandé à zéro n'est jamais construit, même quand la flotte n'en compte aucun
+#[test]
+fn test_ratio_policy_with_zero_share() {
+    let rich = stocked(100, 100);
+    let only_explorers = ProductionConfig {
+        policy: ProductionPolicy::KeepRatio { explorers: 1, miners: 0 },
+        ..default()
+    };
+    assert_eq!(next_robot(&only_explorers, 0, 0, &rich), Some(RobotKind::Explorer));
+    assert_eq!(next_robot(&only_explorers, 2, 3, &rich), Some(RobotKind::Explorer));
+
+    let only_miners = ProductionConfig {
+        policy: ProductionPolicy::KeepRatio { explorers: 0, miners: 1 },
+        ..default()
+    };
+    assert_eq!(next_robot(&only_miners, 0, 0, &rich), Some(RobotKind::Miner));
+    assert_eq!(next_robot(&only_miners, 3, 2, &rich), Some(RobotKind::Miner));
+}
+
+#[test]
+fn test_threshold_policy_keeps_reserve() {
+    let config = ProductionConfig {
+        policy: ProductionPolicy::StockThreshold {
+            kind: RobotKind::Miner,
+            energy: 0,
+            minerals: 20,
+        },
+        miner_cost: RobotCost { energy: 0, minerals: 5 },
+        ..default()
+    };
+    assert_eq!(next_robot(&config, 1, 3, &stocked(0, 19)), None);
+    assert_eq!(next_robot(&config, 1, 3, &stocked(0, 20)), Some(RobotKind::Miner));
+}
+
+// Les unités rapportées financent de nouveaux mineurs qui sortent de la base après le temps de construction
+#[test]
+fn test_station_grows_fleet_from_stock() {
+    let mut app = headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(6),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        production: ProductionConfig {
+            policy: ProductionPolicy::StockThreshold {
+                kind: RobotKind::Miner,
+                energy: 0,
+                minerals: 0,
+            },
+            miner_cost: RobotCost { energy: 1, minerals: 1 },
+            build_time: 5.0,
+            queue_limit: 1,
+            max_fleet: 5,
+            ..default()
+        },
+        ..Scenario::default()
+    });
+
+    let mut launched_at = None;
+    while app.world.resource::<SimulationStats>().robots_built == 0 {
+        let elapsed = app.world.resource::<SimulationStats>().elapsed;
+        assert!(elapsed < 300.0, "aucun robot produit");
+        let queued = app.world.resource::<ProductionQueue>().orders.len();
+        assert!(queued <= 1);
+        if queued == 1 && launched_at.is_none() {
+            launched_at = Some(elapsed);
+        }
+        app.update();
+    }
+    app.update();
+
+    let elapsed = app.world.resource::<SimulationStats>().elapsed;
+    assert!(elapsed - launched_at.unwrap() >= 5.0);
+    assert_eq!(app.world.query_filtered::<(), With<Miner>>().iter(&app.world).count(), 4);
+    assert_eq!(app.world.query_filtered::<(), With<Explorer>>().iter(&app.world).count(), 1);
+}
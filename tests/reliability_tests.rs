@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use simulation_robots::headless::headless_app;
+use simulation_robots::motion::{Kinematics, MotionConfig};
+use simulation_robots::reliability::{Fault, Reliability, ReliabilityConfig};
+use simulation_robots::robots::{Deposit, Miner, Station};
+use simulation_robots::scenario::{MapConfig, MapGenerator, ResourcePlacement, Scenario};
+use simulation_robots::sensor::Sensor;
+use simulation_robots::state::RobotState;
+use simulation_robots::stats::SimulationStats;
+
+#[test]
+fn test_wear_faults_and_repair() {
+    let mut reliability = Reliability::default();
+    reliability.record_travel(Vec2::ZERO, 0.01);
+    reliability.record_travel(Vec2::new(30.0, 40.0), 0.01);
+    assert!((reliability.wear - 0.5).abs() < 1e-5);
+    reliability.record_travel(Vec2::new(300.0, 40.0), 0.01);
+    assert_eq!(reliability.wear, 1.0);
+
+    let mut sensor = Sensor::default();
+    let mut kinematics = Kinematics::new(MotionConfig::MINER);
+    reliability.apply_fault(Fault::SensorDark, Some(&mut sensor), &mut kinematics, 0.5);
+    reliability.apply_fault(Fault::DriveDegraded, Some(&mut sensor), &mut kinematics, 0.5);
+    assert!(reliability.needs_repair());
+    assert_eq!(sensor.range, 0.0);
+    assert_eq!(kinematics.config.max_speed, MotionConfig::MINER.max_speed * 0.5);
+
+    reliability.repair(Some(&mut sensor), &mut kinematics);
+    assert!(!reliability.needs_repair());
+    assert_eq!(reliability.wear, 0.0);
+    assert_eq!(sensor.range, Sensor::default().range);
+    assert_eq!(kinematics.config.max_speed, MotionConfig::MINER.max_speed);
+}
+
+#[test]
+fn test_repairing_transitions() {
+    let repairing = RobotState::Repairing { progress: 0.3 };
+    assert!(RobotState::Returning.can_transition_to(&repairing));
+    assert!(RobotState::Unloading.can_transition_to(&repairing));
+    assert!(repairing.can_transition_to(&RobotState::Idle));
+    assert!(!repairing.can_transition_to(&RobotState::Exploring));
+    assert!(!repairing.is_busy());
+}
+
+// Avec des pannes fréquentes, les robots se font réparer à la base et la collecte continue
+#[test]
+fn test_failing_robots_are_repaired_and_work_goes_on() {
+    let scenario = Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(12),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        reliability: ReliabilityConfig {
+            wear_per_distance: 0.002,
+            failure_rate: 0.05,
+            repair_cost: 1,
+            repair_time: 2.0,
+            ..default()
+        },
+        ..Scenario::default()
+    };
+    let mut app = headless_app(scenario);
+    while app.world.resource::<SimulationStats>().elapsed < 150.0 {
+        app.update();
+    }
+
+    let stats = app.world.resource::<SimulationStats>();
+    assert!(stats.failures > 0);
+    assert!(stats.repairs > 0);
+    assert_eq!(stats.illegal_transitions, 0);
+    assert!(stats.units_collected() > 0);
+    let station = app.world.resource::<Station>();
+    assert!(stats.minerals_collected >= station.minerals + stats.repairs);
+}
+
+// Un mineur tombé en panne pendant la collecte laisse son gisement, que les autres mineurs terminent
+#[test]
+fn test_other_miners_finish_a_failed_miner_deposit() {
+    let scenario = Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(5),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        resources: ResourcePlacement::Fixed {
+            energy: vec![],
+            minerals: vec![(120.0, 60.0)],
+            scientific_sites: vec![],
+            remaining: vec![],
+        },
+        reliability: ReliabilityConfig {
+            repair_cost: 0,
+            repair_time: 10_000.0,
+            ..default()
+        },
+        ..Scenario::default()
+    };
+    let mut app = headless_app(scenario);
+
+    let failed = loop {
+        app.update();
+        assert!(app.world.resource::<SimulationStats>().elapsed < 60.0, "aucun mineur n'a commencé la collecte");
+        let collecting = app
+            .world
+            .query_filtered::<(Entity, &RobotState), With<Miner>>()
+            .iter(&app.world)
+            .find(|(_, state)| matches!(state, RobotState::Collecting { .. }))
+            .map(|(entity, _)| entity);
+        if let Some(entity) = collecting {
+            break entity;
+        }
+    };
+    let (mut reliability, mut kinematics) = app
+        .world
+        .query::<(&mut Reliability, &mut Kinematics)>()
+        .get_mut(&mut app.world, failed)
+        .unwrap();
+    reliability.apply_fault(Fault::DriveDegraded, None, &mut kinematics, 0.5);
+
+    while app.world.query::<&Deposit>().iter(&app.world).next().is_some() {
+        app.update();
+        assert!(app.world.resource::<SimulationStats>().elapsed < 300.0, "le gisement n'a pas été terminé");
+    }
+
+    let stats = app.world.resource::<SimulationStats>();
+    assert_eq!(stats.deposits_exhausted, 1);
+    assert_eq!(stats.illegal_transitions, 0);
+    assert!(matches!(app.world.get::<RobotState>(failed).unwrap(), RobotState::Repairing { .. }));
+}
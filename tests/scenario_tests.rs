@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use simulation_robots::headless::headless_app;
+use simulation_robots::robots::{Base, Explorer, Miner};
+use simulation_robots::scenario::{Scenario, ScenarioError};
+
+// Tous les scénarios fournis doivent se charger et être valides
+#[test]
+fn test_bundled_scenarios_load() {
+    let mut count = 0;
+    for entry in std::fs::read_dir("scenarios").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "ron") {
+            let scenario = Scenario::load(&path)
+                .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+            assert!(!scenario.name.is_empty());
+            count += 1;
+        }
+    }
+    assert!(count >= 4, "scénarios manquants: {}", count);
+}
+
+// Le fichier par défaut reproduit la configuration codée en dur
+#[test]
+fn test_default_file_matches_builtin() {
+    let from_file = Scenario::load("scenarios/default.ron").unwrap();
+    let builtin = Scenario::default();
+
+    assert_eq!(from_file.base_positions(), builtin.base_positions());
+    assert_eq!(from_file.exploration_mode, builtin.exploration_mode);
+    let positions = |scenario: &Scenario| -> Vec<Vec2> {
+        scenario.robots.iter().flat_map(|spec| spec.positions()).collect()
+    };
+    assert_eq!(positions(&from_file), positions(&builtin));
+    assert_eq!(
+        from_file.resources.place(&from_file.map, &from_file.base_positions(), 1).mineral_positions,
+        builtin.resources.place(&builtin.map, &builtin.base_positions(), 1).mineral_positions
+    );
+}
+
+#[test]
+fn test_invalid_scenarios_rejected() {
+    let valid = r#"(
+        name: "Test",
+        map: (width: 800.0, height: 600.0),
+        bases: [(position: (0.0, 0.0))],
+        robots: [(kind: Miner, position: (0.0, -30.0))],
+        resources: Fixed(energy: [], minerals: [(100.0, 0.0)]),
+    )"#;
+    assert!(Scenario::from_ron(valid).is_ok());
+    assert!(Scenario::from_ron(&valid.replace("(100.0, 0.0)]", "(100.0, 0.0)], remaining: [((100.0, 0.0), 3)]")).is_ok());
+
+    let cases = [
+        ("bases: [(position: (0.0, 0.0))]", "bases: [(position: (900.0, 0.0))]"),
+        ("bases: [(position: (0.0, 0.0))]", "bases: []"),
+        ("robots: [(kind: Miner, position: (0.0, -30.0))]", "robots: []"),
+        ("(kind: Miner, position: (0.0, -30.0))", "(kind: Miner, position: (0.0, -30.0), count: 0)"),
+        ("(kind: Miner, position: (0.0, -30.0))", "(kind: Miner, position: (0.0, -30.0), radius: Some(-1.0))"),
+        ("minerals: [(100.0, 0.0)]", "minerals: [(100.0, 5000.0)]"),
+        (
+            "Fixed(energy: [], minerals: [(100.0, 0.0)])",
+            "Random(energy: 3, minerals: 3, min_distance_from_base: 400.0)",
+        ),
+        ("minerals: [(100.0, 0.0)]", "minerals: [(100.0, 0.0)], remaining: [((50.0, 0.0), 3)]"),
+        ("minerals: [(100.0, 0.0)]", "minerals: [(100.0, 0.0)], remaining: [((100.0, 0.0), 0)]"),
+        ("minerals: [(100.0, 0.0)]", "minerals: [(100.0, 0.0)], remaining: [((100.0, 0.0), 20)]"),
+    ];
+    for (from, to) in cases {
+        let text = valid.replace(from, to);
+        assert!(
+            matches!(Scenario::from_ron(&text), Err(ScenarioError::Invalid(_))),
+            "accepté à tort: {}",
+            to
+        );
+    }
+
+    assert!(matches!(Scenario::from_ron("(name: \"Test\")"), Err(ScenarioError::Parse(_))));
+    assert!(matches!(Scenario::load("scenarios/absent.ron"), Err(ScenarioError::Io(_))));
+}
+
+// Le placement aléatoire est reproductible et respecte la distance aux bases
+#[test]
+fn test_random_resources_deterministic() {
+    let scenario = Scenario::load("scenarios/random_resources.ron").unwrap();
+    let bases = scenario.base_positions();
+    let first = scenario.resources.place(&scenario.map, &bases, 11);
+    let second = scenario.resources.place(&scenario.map, &bases, 11);
+
+    assert_eq!(first.energy_positions, second.energy_positions);
+    assert_eq!(first.mineral_positions.len(), 10);
+    assert_eq!(first.scientific_sites.len(), 2);
+    for point in first.energy_positions.iter().chain(&first.mineral_positions) {
+        assert!(point.length() >= 150.0);
+    }
+}
+
+// Plusieurs bases et explorateurs sont créés depuis le scénario
+#[test]
+fn test_scenario_spawns_roster() {
+    let scenario = Scenario::load("scenarios/empty_arena.ron").unwrap().with_seed(3);
+    let mut app = headless_app(scenario);
+    app.update();
+
+    let world = &mut app.world;
+    assert_eq!(world.query::<&Base>().iter(world).count(), 2);
+    assert_eq!(world.query::<&Explorer>().iter(world).count(), 1);
+    assert_eq!(world.query::<&Miner>().iter(world).count(), 4);
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use simulation_robots::behavior::{BehaviorConfig, TreeSource};
+use simulation_robots::comms::{CommsConfig, CommsModel};
+use simulation_robots::headless::headless_app;
+use simulation_robots::robots::{Deposit, Explorer, Miner, Resource, Station};
+use simulation_robots::scenario::{MapConfig, MapGenerator, ResourcePlacement, Scenario, ScenarioError};
+use simulation_robots::scripting::{RobotScript, ScriptConfig, ScriptSource};
+use simulation_robots::state::RobotState;
+use simulation_robots::stats::SimulationStats;
+
+fn scripted_app(scripts: ScriptConfig) -> App {
+    headless_app(Scenario {
+        map: MapConfig {
+            width: 800.0,
+            height: 600.0,
+            seed: Some(4),
+            generator: MapGenerator::Empty,
+            ..Scenario::default().map
+        },
+        scripts,
+        ..Scenario::default()
+    })
+}
+
+fn run(app: &mut App, seconds: f32) {
+    while app.world.resource::<SimulationStats>().elapsed < seconds {
+        app.update();
+    }
+}
+
+fn explorer_script(text: &str) -> ScriptConfig {
+    ScriptConfig {
+        explorer: Some(ScriptSource::Inline(text.into())),
+        ..default()
+    }
+}
+
+#[test]
+fn test_invalid_scripts_rejected() {
+    let scenario = |scripts: ScriptConfig| Scenario {
+        scripts,
+        ..Scenario::default()
+    };
+    let unfinished = scenario(explorer_script("if robot.cargo > 0 {"));
+    assert!(matches!(unfinished.validate(), Err(ScenarioError::Invalid(_))));
+
+    let missing = scenario(ScriptConfig {
+        miner: Some(ScriptSource::File("scripts/absent.rhai".into())),
+        ..default()
+    });
+    assert!(matches!(missing.validate(), Err(ScenarioError::Io(_))));
+
+    let no_budget = scenario(ScriptConfig {
+        max_operations: 0,
+        ..explorer_script("\"wander\"")
+    });
+    assert!(matches!(no_budget.validate(), Err(ScenarioError::Invalid(_))));
+
+    let both = Scenario {
+        behaviors: BehaviorConfig {
+            explorer: Some(TreeSource::Default),
+            ..default()
+        },
+        ..scenario(explorer_script("\"wander\""))
+    };
+    assert!(matches!(both.validate(), Err(ScenarioError::Invalid(_))));
+}
+
+// Le script voit la perception du robot et garde sa mémoire d'un pas à l'autre
+#[test]
+fn test_perception_and_memory() {
+    let mut app = scripted_app(explorer_script(
+        r#"
+        memory.ticks = (memory.ticks ?? 0) + 1;
+        memory.kind = robot.kind;
+        memory.rows = robot.cells.len();
+        memory.row_width = robot.cells[0].len();
+        memory.base = robot.base.distance;
+        memory.battery = robot.battery;
+        memory.at_base = robot.at_base;
+        "wait"
+        "#,
+    ));
+    run(&mut app, 1.0);
+
+    let script = app.world.query_filtered::<&RobotScript, With<Explorer>>().single(&app.world);
+    assert!(script.memory["ticks"].as_int().unwrap() > 1);
+    assert_eq!(script.memory["kind"].clone().into_string().unwrap(), "explorer");
+    assert_eq!(script.memory["rows"].as_int().unwrap(), 7);
+    assert_eq!(script.memory["row_width"].as_int().unwrap(), 7);
+    assert!((script.memory["base"].as_float().unwrap() - 50.0).abs() < 1.0);
+    assert_eq!(script.memory["battery"].as_float().unwrap(), 1.0);
+    assert!(!script.memory["at_base"].as_bool().unwrap());
+    assert_eq!(app.world.query::<&RobotScript>().iter(&app.world).count(), 1);
+}
+
+// En maillage radio, le script ne voit pas les gisements connus de la seule station
+#[test]
+fn test_perception_follows_radio_model() {
+    let known_resources = |comms: CommsConfig| {
+        let mut app = headless_app(Scenario {
+            map: MapConfig {
+                width: 800.0,
+                height: 600.0,
+                seed: Some(4),
+                generator: MapGenerator::Empty,
+                ..Scenario::default().map
+            },
+            resources: ResourcePlacement::Fixed {
+                energy: vec![],
+                minerals: vec![(350.0, -250.0)],
+                scientific_sites: vec![],
+                remaining: vec![],
+            },
+            robots: Scenario::default().robots.into_iter().take(1).collect(),
+            scripts: explorer_script("memory.resources = robot.resources.len(); \"wait\""),
+            comms,
+            ..Scenario::default()
+        });
+        app.update();
+        let (deposit, remaining) = {
+            let (entity, deposit) = app.world.query::<(Entity, &Deposit)>().single(&app.world);
+            (entity, deposit.remaining)
+        };
+        app.world
+            .resource_mut::<Station>()
+            .register_deposit(deposit, Vec2::new(350.0, -250.0), Resource::Mineral, remaining);
+        run(&mut app, 1.0);
+        let script = app.world.query_filtered::<&RobotScript, With<Explorer>>().single(&app.world);
+        script.memory["resources"].as_int().unwrap()
+    };
+
+    assert_eq!(known_resources(CommsConfig::default()), 1);
+    let mesh = CommsConfig {
+        model: CommsModel::Mesh,
+        ..default()
+    };
+    assert_eq!(known_resources(mesh), 0);
+}
+
+// `move_to` passe par le même code de déplacement que les autres stratégies
+#[test]
+fn test_move_to_action() {
+    let mut app = scripted_app(explorer_script(
+        r#"
+        if robot.state == "MovingTo" && robot.last_status == "success" {
+            memory.arrived = true;
+        }
+        if memory.arrived ?? false {
+            return "wait";
+        }
+        #{ action: "move_to", x: 200.0, y: 100.0 }
+        "#,
+    ));
+    run(&mut app, 15.0);
+
+    let (transform, state) = app
+        .world
+        .query_filtered::<(&Transform, &RobotState), With<Explorer>>()
+        .single(&app.world);
+    assert_eq!(*state, RobotState::Idle);
+    assert!(transform.translation.truncate().distance(Vec2::new(200.0, 100.0)) < 15.0);
+    let stats = app.world.resource::<SimulationStats>();
+    assert_eq!(stats.script_errors, 0);
+    assert_eq!(stats.illegal_transitions, 0);
+}
+
+// Un script qui dépasse son budget est interrompu et le robot reste sur place
+#[test]
+fn test_operation_budget() {
+    let mut app = scripted_app(ScriptConfig {
+        max_operations: 500,
+        ..explorer_script("let n = 0; loop { n += 1; } \"wander\"")
+    });
+    run(&mut app, 2.0);
+
+    let (transform, script) = app
+        .world
+        .query_filtered::<(&Transform, &RobotScript), With<Explorer>>()
+        .single(&app.world);
+    assert_eq!(transform.translation.truncate(), Vec2::new(0.0, 50.0));
+    assert!(script.errors > 1);
+    assert_eq!(app.world.resource::<SimulationStats>().script_errors, script.errors);
+}
+
+// Le script de mineur livré ramène des ressources à la station
+#[test]
+fn test_bundled_miner_script() {
+    let mut app = scripted_app(ScriptConfig {
+        miner: Some(ScriptSource::File("scripts/miner.rhai".into())),
+        ..default()
+    });
+    run(&mut app, 90.0);
+
+    assert_eq!(app.world.query_filtered::<&RobotScript, With<Miner>>().iter(&app.world).count(), 3);
+    let stats = app.world.resource::<SimulationStats>();
+    assert!(stats.units_collected() > 0);
+    assert_eq!(stats.script_errors, 0);
+    assert_eq!(stats.illegal_transitions, 0);
+}
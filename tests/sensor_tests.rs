@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use simulation_robots::robots::GameMap;
+use simulation_robots::sensor::{has_line_of_sight, heading_from_rotation, Sensor};
+
+// Carte 10x10 avec un mur vertical en x = 6
+fn walled_map() -> GameMap {
+    let mut obstacles = vec![vec![false; 10]; 10];
+    for row in obstacles.iter_mut().take(9).skip(1) {
+        row[6] = true;
+    }
+
+    GameMap {
+        size: Vec2::new(100.0, 100.0),
+        cell_size: 10.0,
+        obstacles,
+        seed: 42,
+    }
+}
+
+// Test de la ligne de vue à travers un mur
+#[test]
+fn test_line_of_sight_blocked_by_wall() {
+    let map = walled_map();
+
+    assert!(has_line_of_sight(Vec2::new(-15.0, 5.0), Vec2::new(5.0, 5.0), &map));
+    assert!(!has_line_of_sight(Vec2::new(-15.0, 5.0), Vec2::new(25.0, 5.0), &map));
+    assert!(has_line_of_sight(Vec2::new(-15.0, 5.0), Vec2::new(15.0, 5.0), &map));
+}
+
+// Test des cellules visibles: le mur est vu, pas ce qui est derrière
+#[test]
+fn test_visible_cells_stop_at_walls() {
+    let map = walled_map();
+    let sensor = Sensor {
+        range: 60.0,
+        field_of_view: std::f32::consts::TAU,
+        ..default()
+    };
+
+    let origin = Vec2::new(-5.0, 5.0);
+    let cells = sensor.visible_cells(origin, Vec2::X, &map);
+
+    assert!(cells.contains(&map.world_to_cell(origin)));
+    assert!(cells.contains(&IVec2::new(6, 5)));
+    assert!(!cells.contains(&IVec2::new(7, 5)));
+    assert!(cells.contains(&IVec2::new(1, 5)));
+}
+
+// Test du champ de vision
+#[test]
+fn test_field_of_view() {
+    let map = walled_map();
+    let sensor = Sensor {
+        range: 60.0,
+        field_of_view: 90f32.to_radians(),
+        ..default()
+    };
+
+    let origin = Vec2::new(-20.0, 0.0);
+    assert!(sensor.can_see(origin, Vec2::Y, Vec2::new(-20.0, 30.0), &map));
+    assert!(!sensor.can_see(origin, Vec2::Y, Vec2::new(-20.0, -30.0), &map));
+    assert!(!sensor.can_see(origin, Vec2::Y, Vec2::new(-20.0, 90.0), &map));
+}
+
+// Test de l'orientation déduite de la rotation des robots
+#[test]
+fn test_heading_from_rotation() {
+    let direction = Vec2::new(0.6, -0.8);
+    let rotation = Quat::from_rotation_z(-direction.y.atan2(direction.x));
+    let heading = heading_from_rotation(rotation);
+
+    assert!((heading - direction).length() < 0.001);
+    assert!((heading_from_rotation(Quat::IDENTITY) - Vec2::X).length() < 0.001);
+}
+
+// Test du bruit et des faux négatifs
+#[test]
+fn test_sensor_noise() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let target = Vec2::new(10.0, 10.0);
+
+    let blind = Sensor { false_negative_rate: 1.0, ..default() };
+    assert!(blind.detect(target, &mut rng).is_none());
+
+    let noisy = Sensor { position_noise: 5.0, ..default() };
+    for _ in 0..100 {
+        let measured = noisy.detect(target, &mut rng).unwrap();
+        assert!(measured.distance(target) <= 5.0);
+    }
+}
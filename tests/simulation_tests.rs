@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 use simulation_robots::robots::{
-    GameMap, MapResources, 
+    GameMap, MapResources, ExploredZones,
     is_position_blocked, rotate_vec2, 
     move_entity_avoiding_obstacles, 
-    generate_map, clear_obstacles_around_resources
+    generate_map, clear_obstacles_around_resources,
+    overlap_correction,
 };
 
 // Test de la fonction is_position_blocked
@@ -54,7 +55,7 @@ fn test_movement_with_obstacles() {
     
     let current_pos = Vec3::new(0.0, 0.0, 0.0);
     let target_pos = Vec3::new(10.0, 0.0, 0.0);
-    let (new_pos, _) = move_entity_avoiding_obstacles(current_pos, target_pos, 10.0, 1.0, &game_map);
+    let (new_pos, _) = move_entity_avoiding_obstacles(current_pos, target_pos, 10.0, 1.0, 7.0, &game_map);
     
     assert_ne!(new_pos, current_pos);
     
@@ -112,4 +113,29 @@ fn test_clear_obstacles() {
     assert!(grid_y2 < game_map.obstacles.len(), "grid_y2 out of bounds: {} >= {}", grid_y2, game_map.obstacles.len());
     
     assert_eq!(game_map.obstacles[grid_y2][grid_x2], false);
-}
\ No newline at end of file
+}
+// Test de la taille de ExploredZones sur une grande carte
+#[test]
+fn test_explored_zones_large_map() {
+    let map = generate_map(4000.0, 4000.0, 20.0, 7);
+    let explored = ExploredZones::for_map(&map);
+
+    assert_eq!(explored.grid.len(), map.obstacles.len());
+    assert_eq!(explored.grid[0].len(), map.obstacles[0].len());
+    assert_eq!(explored.cell_size, map.cell_size);
+    assert_eq!(explored.explored_ratio(), 0.0);
+}
+
+// Test de la correction de chevauchement entre deux robots
+#[test]
+fn test_overlap_correction() {
+    let correction = overlap_correction(Vec2::new(0.0, 0.0), 6.0, Vec2::new(8.0, 0.0), 6.0);
+    assert!((correction.x + 2.0).abs() < 0.001);
+    assert!(correction.y.abs() < 0.001);
+
+    let correction = overlap_correction(Vec2::new(0.0, 0.0), 6.0, Vec2::new(20.0, 0.0), 6.0);
+    assert_eq!(correction, Vec2::ZERO);
+
+    let correction = overlap_correction(Vec2::new(5.0, 5.0), 6.0, Vec2::new(5.0, 5.0), 6.0);
+    assert!((correction.length() - 6.0).abs() < 0.001);
+}
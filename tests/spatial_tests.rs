@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use simulation_robots::spatial::{SpatialIndex, SpatialKind};
+
+// Test de la requête par rayon
+#[test]
+fn test_query_radius() {
+    let mut index = SpatialIndex::new(50.0);
+    index.insert(Entity::from_raw(1), Vec2::new(10.0, 10.0), SpatialKind::Resource);
+    index.insert(Entity::from_raw(2), Vec2::new(60.0, 0.0), SpatialKind::Resource);
+    index.insert(Entity::from_raw(3), Vec2::new(-200.0, 0.0), SpatialKind::Resource);
+    index.insert(Entity::from_raw(4), Vec2::new(5.0, 0.0), SpatialKind::Robot);
+
+    let found = index.query_radius(Vec2::ZERO, 70.0, SpatialKind::Resource);
+    let entities: Vec<Entity> = found.iter().map(|(entity, _)| *entity).collect();
+
+    assert_eq!(entities, vec![Entity::from_raw(1), Entity::from_raw(2)]);
+}
+
+// Test du plus proche voisin après déplacement et suppression
+#[test]
+fn test_nearest_with_updates() {
+    let mut index = SpatialIndex::new(50.0);
+    index.insert(Entity::from_raw(1), Vec2::new(100.0, 0.0), SpatialKind::Resource);
+    index.insert(Entity::from_raw(2), Vec2::new(300.0, 0.0), SpatialKind::Resource);
+
+    let (nearest, _, distance) = index.nearest(Vec2::ZERO, 1000.0, SpatialKind::Resource).unwrap();
+    assert_eq!(nearest, Entity::from_raw(1));
+    assert!((distance - 100.0).abs() < 0.001);
+
+    index.insert(Entity::from_raw(2), Vec2::new(-20.0, 0.0), SpatialKind::Resource);
+    let (nearest, _, _) = index.nearest(Vec2::ZERO, 1000.0, SpatialKind::Resource).unwrap();
+    assert_eq!(nearest, Entity::from_raw(2));
+
+    index.remove(Entity::from_raw(2));
+    index.remove(Entity::from_raw(1));
+    assert!(index.nearest(Vec2::ZERO, 1000.0, SpatialKind::Resource).is_none());
+    assert!(index.is_empty());
+
+    index.insert(Entity::from_raw(3), Vec2::new(30.0, 0.0), SpatialKind::Resource);
+    assert!(index.nearest(Vec2::ZERO, 20.0, SpatialKind::Resource).is_none());
+}
+
+// Comparaison avec une recherche linéaire sur beaucoup de gisements
+#[test]
+fn test_nearest_matches_linear_scan() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut index = SpatialIndex::new(40.0);
+    let mut points = Vec::new();
+
+    for i in 0..2000 {
+        let pos = Vec2::new(rng.gen_range(-2000.0..2000.0), rng.gen_range(-2000.0..2000.0));
+        index.insert(Entity::from_raw(i), pos, SpatialKind::Resource);
+        points.push((Entity::from_raw(i), pos));
+    }
+
+    for _ in 0..100 {
+        let center = Vec2::new(rng.gen_range(-2000.0..2000.0), rng.gen_range(-2000.0..2000.0));
+        let expected = points.iter()
+            .min_by(|a, b| a.1.distance(center).total_cmp(&b.1.distance(center)))
+            .unwrap();
+        let (nearest, _, _) = index.nearest(center, f32::INFINITY, SpatialKind::Resource).unwrap();
+        assert_eq!(nearest, expected.0);
+
+        let in_radius = index.query_radius(center, 150.0, SpatialKind::Resource).len();
+        let expected_in_radius = points.iter().filter(|(_, pos)| pos.distance(center) <= 150.0).count();
+        assert_eq!(in_radius, expected_in_radius);
+    }
+}
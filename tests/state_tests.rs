@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use simulation_robots::state::{
+    miner_step, MinerAction, MinerSituation, RobotState, COLLECTION_TIME,
+};
+
+fn situation(position: Vec2, target: Option<Vec2>, deposit_available: bool) -> MinerSituation {
+    MinerSituation {
+        position,
+        base: Vec2::ZERO,
+        target,
+        deposit_available,
+        collection_time: COLLECTION_TIME,
+    }
+}
+
+// Cycle complet d'un mineur: départ, collecte, retour, déchargement
+#[test]
+fn test_miner_full_cycle() {
+    let deposit = Vec2::new(100.0, 0.0);
+
+    let (state, action) = miner_step(RobotState::Idle, &situation(Vec2::ZERO, Some(deposit), true), 0.1);
+    assert_eq!(state, RobotState::MovingTo(deposit));
+    assert_eq!(action, MinerAction::MoveTowards(deposit));
+
+    let (state, _) = miner_step(state, &situation(Vec2::new(50.0, 0.0), Some(deposit), true), 0.1);
+    assert_eq!(state, RobotState::MovingTo(deposit));
+
+    let (mut state, action) = miner_step(state, &situation(Vec2::new(95.0, 0.0), Some(deposit), true), 0.1);
+    assert_eq!(state, RobotState::Collecting { progress: 0.0 });
+    assert_eq!(action, MinerAction::Wait);
+
+    let mut loaded = false;
+    for _ in 0..(COLLECTION_TIME / 0.1) as usize + 1 {
+        let (next, action) = miner_step(state, &situation(Vec2::new(95.0, 0.0), Some(deposit), true), 0.1);
+        state = next;
+        if action == MinerAction::Load {
+            loaded = true;
+            break;
+        }
+    }
+    assert!(loaded);
+    assert_eq!(state, RobotState::Returning);
+
+    let (state, action) = miner_step(state, &situation(Vec2::new(50.0, 0.0), Some(deposit), true), 0.1);
+    assert_eq!(state, RobotState::Returning);
+    assert_eq!(action, MinerAction::MoveTowards(Vec2::ZERO));
+
+    let (state, action) = miner_step(state, &situation(Vec2::new(5.0, 0.0), Some(deposit), true), 0.1);
+    assert_eq!(state, RobotState::Unloading);
+    assert_eq!(action, MinerAction::Unload);
+
+    let (state, _) = miner_step(state, &situation(Vec2::new(5.0, 0.0), Some(deposit), true), 0.1);
+    assert_eq!(state, RobotState::MovingTo(deposit));
+
+    let (state, _) = miner_step(RobotState::Unloading, &situation(Vec2::new(5.0, 0.0), None, false), 0.1);
+    assert_eq!(state, RobotState::Idle);
+}
+
+// Un gisement épuisé renvoie les mineurs à la base
+#[test]
+fn test_miner_returns_when_deposit_exhausted() {
+    let deposit = Vec2::new(100.0, 0.0);
+
+    let (state, _) = miner_step(RobotState::MovingTo(deposit), &situation(Vec2::new(50.0, 0.0), Some(deposit), false), 0.1);
+    assert_eq!(state, RobotState::Returning);
+
+    let (state, action) = miner_step(
+        RobotState::Collecting { progress: 0.5 },
+        &situation(Vec2::new(95.0, 0.0), Some(deposit), false),
+        0.1,
+    );
+    assert_eq!(state, RobotState::Returning);
+    assert_ne!(action, MinerAction::Load);
+}
+
+// Table des transitions autorisées
+#[test]
+fn test_transition_table() {
+    let target = Vec2::new(10.0, 0.0);
+
+    assert!(RobotState::Idle.can_transition_to(&RobotState::MovingTo(target)));
+    assert!(RobotState::MovingTo(target).can_transition_to(&RobotState::Collecting { progress: 0.0 }));
+    assert!(RobotState::Collecting { progress: 0.2 }.can_transition_to(&RobotState::Collecting { progress: 0.4 }));
+    assert!(RobotState::Returning.can_transition_to(&RobotState::Unloading));
+    assert!(RobotState::Exploring.can_transition_to(&RobotState::Stuck));
+
+    assert!(!RobotState::Idle.can_transition_to(&RobotState::Collecting { progress: 0.0 }));
+    assert!(!RobotState::Collecting { progress: 0.5 }.can_transition_to(&RobotState::Unloading));
+    assert!(!RobotState::Returning.can_transition_to(&RobotState::Collecting { progress: 0.0 }));
+    assert!(!RobotState::Unloading.can_transition_to(&RobotState::Returning));
+}
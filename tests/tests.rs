@@ -55,7 +55,7 @@ fn test_movement_with_obstacles() {
     
     let current_pos = Vec3::new(0.0, 0.0, 0.0);
     let target_pos = Vec3::new(10.0, 0.0, 0.0);
-    let (new_pos, _) = move_entity_avoiding_obstacles(current_pos, target_pos, 10.0, 1.0, &game_map);
+    let (new_pos, _) = move_entity_avoiding_obstacles(current_pos, target_pos, 10.0, 1.0, 7.0, &game_map);
     
     assert_ne!(new_pos, current_pos);
     
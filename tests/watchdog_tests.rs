@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use simulation_robots::watchdog::{StuckSettings, StuckWatchdog};
+
+// Un robot immobile est signalé après le délai configuré
+#[test]
+fn test_watchdog_detects_lack_of_progress() {
+    let settings = StuckSettings { timeout: 1.0, ..default() };
+    let goal = Some(Vec2::new(100.0, 0.0));
+    let mut watchdog = StuckWatchdog::default();
+
+    assert!(!watchdog.update(Vec2::ZERO, goal, 0.1, &settings));
+    for _ in 0..9 {
+        assert!(!watchdog.update(Vec2::ZERO, goal, 0.1, &settings));
+    }
+    assert!(watchdog.update(Vec2::ZERO, goal, 0.1, &settings));
+}
+
+// Avancer vers l'objectif ou changer d'objectif remet le compteur à zéro
+#[test]
+fn test_watchdog_resets_on_progress() {
+    let settings = StuckSettings { timeout: 1.0, ..default() };
+    let goal = Some(Vec2::new(100.0, 0.0));
+    let mut watchdog = StuckWatchdog::default();
+
+    let mut position = Vec2::ZERO;
+    watchdog.update(position, goal, 0.1, &settings);
+    for _ in 0..30 {
+        position.x += 3.0;
+        assert!(!watchdog.update(position, goal, 0.1, &settings));
+    }
+
+    // Tourner en rond sans se rapprocher n'est pas un progrès
+    for i in 0..10 {
+        let wobble = Vec2::new(0.0, if i % 2 == 0 { 5.0 } else { -5.0 });
+        watchdog.update(position + wobble, goal, 0.1, &settings);
+    }
+    assert!(watchdog.stalled_for >= 0.9);
+
+    watchdog.attempts = 2;
+    assert!(!watchdog.update(position, Some(Vec2::new(-100.0, 0.0)), 0.1, &settings));
+    assert_eq!(watchdog.attempts, 0);
+    assert_eq!(watchdog.stalled_for, 0.0);
+}